@@ -3,6 +3,7 @@
 use sparsey::prelude::*;
 
 #[derive(Clone, Copy, Debug)]
+#[allow(dead_code)]
 struct Position(i32, i32);
 
 #[derive(Clone, Copy, Debug)]
@@ -1,4 +1,8 @@
-use crate::entity::{Component, ComponentSparseSet, Entity, EntityStorage, GroupInfo, SparseVec};
+use crate::entity::{
+    Component, ComponentSparseSet, EntitiesIter, Entity, EntityStorage, GroupInfo, OtherComponents,
+    Sample, SparseVec,
+};
+use crate::util::AccessTrace;
 use atomic_refcell::{AtomicRef, AtomicRefMut};
 use std::fmt;
 use std::marker::PhantomData;
@@ -33,6 +37,32 @@ impl<'a> Entities<'a> {
     pub fn as_slice(&self) -> &[Entity] {
         self.entities.entities()
     }
+
+    /// Returns an iterator over all entities in the storage.
+    #[inline]
+    pub fn iter(&self) -> EntitiesIter<'a> {
+        EntitiesIter::new(self.entities.entities())
+    }
+}
+
+impl<'a> IntoIterator for Entities<'a> {
+    type Item = Entity;
+    type IntoIter = EntitiesIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Entities<'a> {
+    type Item = Entity;
+    type IntoIter = EntitiesIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl fmt::Debug for Entities<'_> {
@@ -45,6 +75,7 @@ impl fmt::Debug for Entities<'_> {
 pub struct Comp<'a, T> {
     components: AtomicRef<'a, ComponentSparseSet>,
     group_info: Option<GroupInfo<'a>>,
+    _trace: AccessTrace,
     _phantom: PhantomData<&'a [T]>,
 }
 
@@ -54,10 +85,12 @@ impl<'a, T> Comp<'a, T> {
     pub(crate) unsafe fn new(
         components: AtomicRef<'a, ComponentSparseSet>,
         group_info: Option<GroupInfo<'a>>,
+        trace: AccessTrace,
     ) -> Self {
         Self {
             components,
             group_info,
+            _trace: trace,
             _phantom: PhantomData,
         }
     }
@@ -67,6 +100,7 @@ impl<'a, T> Comp<'a, T> {
 pub struct CompMut<'a, T> {
     components: AtomicRefMut<'a, ComponentSparseSet>,
     group_info: Option<GroupInfo<'a>>,
+    _trace: AccessTrace,
     _phantom: PhantomData<&'a mut [T]>,
 }
 
@@ -79,17 +113,19 @@ where
     pub(crate) unsafe fn new(
         components: AtomicRefMut<'a, ComponentSparseSet>,
         group_info: Option<GroupInfo<'a>>,
+        trace: AccessTrace,
     ) -> Self {
         Self {
             components,
             group_info,
+            _trace: trace,
             _phantom: PhantomData,
         }
     }
 
     /// Returns a mutable reference to the component mapped to `entity` if it exists.
     #[must_use]
-    pub fn get_mut(&self, entity: Entity) -> Option<&mut T> {
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
         unsafe { self.components.get_mut(entity) }
     }
 
@@ -104,6 +140,56 @@ where
     pub fn split_mut(&mut self) -> (&[Entity], &SparseVec, &mut [T]) {
         unsafe { self.components.split_mut() }
     }
+
+    /// Splits the storage into its entities and mutable components. The component at each
+    /// index belongs to the entity at the same index.
+    #[must_use]
+    pub fn split_dense_mut(&mut self) -> (&[Entity], &mut [T]) {
+        let (entities, _, components) = self.split_mut();
+        (entities, components)
+    }
+
+    /// Runs a function for each entity and mutable component in the storage, along with a shared
+    /// view over all other components of the same type.
+    ///
+    /// Useful for updates that read neighboring components. Changes made to components visited
+    /// earlier are visible in the view.
+    pub fn for_each_with_others<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Entity, &mut T, OtherComponents<'_, T>),
+    {
+        let (entities, sparse, components) = self.split_mut();
+
+        for (index, &entity) in entities.iter().enumerate() {
+            let (before, rest) = components.split_at_mut(index);
+            let (component, after) = rest.split_first_mut().unwrap();
+
+            f(
+                entity,
+                component,
+                OtherComponents::new(index, entities, sparse, before, after),
+            );
+        }
+    }
+}
+
+impl<T> CompMut<'_, T>
+where
+    T: Component + PartialEq,
+{
+    /// Overwrites the component mapped to `entity` with `value` only if the component exists and
+    /// the two are not equal.
+    ///
+    /// Returns whether the component was overwritten.
+    pub fn set_if_neq(&mut self, entity: Entity, value: T) -> bool {
+        match self.get_mut(entity) {
+            Some(component) if *component != value => {
+                *component = value;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl<T> IndexMut<Entity> for CompMut<'_, T>
@@ -145,6 +231,12 @@ macro_rules! impl_comp_common {
                 self.components.is_empty()
             }
 
+            /// Returns the number of components the storage can hold without reallocating.
+            #[must_use]
+            pub fn capacity(&self) -> usize {
+                self.components.capacity()
+            }
+
             /// Returns the group info of the view.
             #[must_use]
             pub fn group_info(&self) -> Option<GroupInfo<'_>> {
@@ -168,6 +260,55 @@ macro_rules! impl_comp_common {
             pub fn split(&self) -> (&[Entity], &SparseVec, &[T]) {
                 unsafe { self.components.split() }
             }
+
+            /// Splits the view into its entities and components. The component at each index
+            /// belongs to the entity at the same index.
+            #[must_use]
+            pub fn split_dense(&self) -> (&[Entity], &[T]) {
+                let (entities, _, components) = self.split();
+                (entities, components)
+            }
+
+            /// Returns an iterator over up to `n` randomly chosen components in the view, along
+            /// with their entities.
+            ///
+            /// Components are chosen uniformly without repetition and returned in storage order,
+            /// which for grouped components changes as entities enter and leave groups. Meant
+            /// for debugging large storages.
+            #[must_use]
+            pub fn sample(&self, n: usize) -> Sample<'_, T> {
+                self.sample_seeded(n, Sample::<T>::random_seed())
+            }
+
+            /// Same as [`sample`](Self::sample), but the chosen components are determined by
+            /// `seed` for reproducible results.
+            #[must_use]
+            pub fn sample_seeded(&self, n: usize, seed: u64) -> Sample<'_, T> {
+                let (entities, _, components) = self.split();
+                Sample::new(entities, components, n, seed)
+            }
+
+            /// Returns an iterator over the entities present in both `self` and `other`, along
+            /// with their components.
+            ///
+            /// Entities are matched by index and version, so both views must come from storages
+            /// that share the same entity space, such as a server and a client replicating the
+            /// same entities.
+            #[must_use = "iterators are lazy and do nothing unless consumed"]
+            pub fn zip_by_entity<'b, U>(
+                &'b self,
+                other: &'b Comp<'_, U>,
+            ) -> impl Iterator<Item = (Entity, &'b T, &'b U)> + 'b
+            where
+                U: Component,
+            {
+                self.entities()
+                    .iter()
+                    .zip(self.as_slice())
+                    .filter_map(|(&entity, component)| {
+                        Some((entity, component, other.get(entity)?))
+                    })
+            }
         }
 
         impl<T> Index<Entity> for $Comp<'_, T>
@@ -1,11 +1,15 @@
 use crate::entity::{
     group, panic_missing_comp, ungroup, Component, Entity, EntityStorage, GroupMask,
 };
+use crate::util::TypeData;
 use std::any::TypeId;
 
 /// Handles insert and remove operations for components stored in an
 /// [`EntitySotrage`](crate::entity::EntityStorage).
 pub unsafe trait ComponentSet {
+    /// Slice containing the component data of the components present in the set.
+    const COMPONENTS: &'static [TypeData];
+
     /// The components returned by [`remove`](Self::remove).
     type Remove;
 
@@ -35,6 +39,10 @@ macro_rules! impl_component_set {
         where
             $($Comp: Component,)*
         {
+            const COMPONENTS: &'static [TypeData] = &[
+                $(TypeData::new::<$Comp>(),)*
+            ];
+
             type Remove = ($(Option<$Comp>,)*);
 
             fn insert(entities: &mut EntityStorage, entity: Entity, components: Self) {
@@ -205,6 +213,8 @@ macro_rules! impl_component_set {
 
 #[allow(unused_variables)]
 unsafe impl ComponentSet for () {
+    const COMPONENTS: &'static [TypeData] = &[];
+
     type Remove = ();
 
     #[inline(always)]
@@ -1,4 +1,5 @@
-use crate::entity::{Component, DenseEntity, Entity, SparseVec};
+use crate::entity::{Component, DenseEntity, DoubleBuffered, Entity, SparseVec};
+use crate::util::TypeData;
 use std::alloc::{alloc, dealloc, handle_alloc_error, Layout, LayoutError};
 use std::ptr::NonNull;
 use std::{fmt, mem, ptr, slice};
@@ -144,6 +145,7 @@ impl ComponentSparseSet {
 
     #[inline]
     #[must_use]
+    #[allow(clippy::mut_from_ref)]
     pub unsafe fn get_mut<T>(&self, entity: Entity) -> Option<&mut T>
     where
         T: Component,
@@ -240,28 +242,115 @@ impl ComponentSparseSet {
         }
     }
 
+    #[inline]
+    pub fn advance(&mut self) {
+        if let Some(advance) = self.vtable.advance {
+            unsafe {
+                advance(self);
+            }
+        }
+    }
+
+    #[inline]
+    pub unsafe fn set_double_buffered<T>(&mut self)
+    where
+        T: Component + Clone,
+    {
+        self.vtable.advance = Some(Self::advance_typed::<T>);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn type_data(&self) -> TypeData {
+        self.vtable.type_data
+    }
+
+    #[must_use]
+    pub fn debug(&self, entity: Entity) -> Option<String> {
+        let debug = self.vtable.debug?;
+        let index = self.sparse.get(entity)?.dense();
+        unsafe { Some(debug(self, index)) }
+    }
+
+    #[inline]
+    pub unsafe fn set_debug<T>(&mut self)
+    where
+        T: Component + fmt::Debug,
+    {
+        self.vtable.debug = Some(Self::debug_typed::<T>);
+    }
+
+    #[must_use]
+    pub fn try_clone(&self) -> Option<Self> {
+        let clone = self.vtable.clone?;
+        unsafe { Some(clone(self)) }
+    }
+
+    #[inline]
+    pub unsafe fn set_clone<T>(&mut self)
+    where
+        T: Component + Clone,
+    {
+        self.vtable.clone = Some(Self::clone_typed::<T>);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        if self.cap - self.len >= additional {
+            return;
+        }
+
+        let new_cap = self
+            .len
+            .checked_add(additional)
+            .expect("Cannot allocate space for more components");
+
+        unsafe {
+            (self.vtable.resize)(self, new_cap);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.sparse.shrink_to_fit();
+
+        if self.cap != self.len {
+            unsafe {
+                (self.vtable.resize)(self, self.len);
+            }
+        }
+    }
+
     #[inline]
     fn grow(&mut self) {
+        let new_cap = match self.cap {
+            0 => 4,
+            cap => cap.saturating_add(cap),
+        };
+
+        assert_ne!(
+            new_cap, self.cap,
+            "Cannot allocate space for more components",
+        );
+
         unsafe {
-            (self.vtable.grow)(self);
+            (self.vtable.resize)(self, new_cap);
         }
     }
 
-    unsafe fn grow_typed<T>(&mut self)
+    unsafe fn resize_typed<T>(&mut self, new_cap: usize)
     where
         T: Component,
     {
-        let (new_entities, new_components, new_cap) = {
-            let new_cap = match self.cap {
-                0 => 4,
-                cap => cap.saturating_add(cap),
-            };
-
-            assert_ne!(
-                new_cap, self.cap,
-                "Cannot allocate space for more components",
-            );
+        debug_assert!(new_cap >= self.len);
 
+        let (new_entities, new_components) = if new_cap == 0 {
+            (NonNull::dangling(), NonNull::<T>::dangling().cast())
+        } else {
             let (new_layout, new_components_offset) =
                 Self::compute_layout::<T>(new_cap).expect("Failed to compute new component layout");
 
@@ -275,7 +364,6 @@ impl ComponentSparseSet {
             (
                 NonNull::new_unchecked(new_data.cast::<Entity>()),
                 NonNull::new_unchecked(new_data.byte_add(new_components_offset)),
-                new_cap,
             )
         };
 
@@ -320,6 +408,52 @@ impl ComponentSparseSet {
         mem::swap(component_a, component_b);
     }
 
+    unsafe fn advance_typed<T>(&mut self)
+    where
+        T: Component + Clone,
+    {
+        self.as_mut_slice::<DoubleBuffered<T>>()
+            .iter_mut()
+            .for_each(DoubleBuffered::advance);
+    }
+
+    unsafe fn clone_typed<T>(&self) -> Self
+    where
+        T: Component + Clone,
+    {
+        let mut clone = Self {
+            sparse: self.sparse.clone(),
+            entities: NonNull::dangling(),
+            components: NonNull::<T>::dangling().cast(),
+            len: 0,
+            cap: 0,
+            vtable: self.vtable,
+        };
+
+        clone.resize_typed::<T>(self.len);
+        ptr::copy_nonoverlapping(self.entities.as_ptr(), clone.entities.as_ptr(), self.len);
+
+        for component in self.as_slice::<T>() {
+            clone
+                .components
+                .cast::<T>()
+                .as_ptr()
+                .add(clone.len)
+                .write(component.clone());
+
+            clone.len += 1;
+        }
+
+        clone
+    }
+
+    unsafe fn debug_typed<T>(&self, index: usize) -> String
+    where
+        T: Component + fmt::Debug,
+    {
+        format!("{:?}", &*self.components.cast::<T>().as_ptr().add(index))
+    }
+
     unsafe fn clear_typed<T>(&mut self)
     where
         T: Component,
@@ -389,11 +523,15 @@ impl fmt::Debug for ComponentSparseSet {
 
 #[derive(Clone, Copy, Debug)]
 struct ComponentSparseSetVtable {
-    grow: unsafe fn(&mut ComponentSparseSet),
+    type_data: TypeData,
+    resize: unsafe fn(&mut ComponentSparseSet, usize),
     swap: unsafe fn(&mut ComponentSparseSet, usize, usize),
     delete: unsafe fn(&mut ComponentSparseSet, Entity),
     clear: unsafe fn(&mut ComponentSparseSet),
     drop: unsafe fn(&mut ComponentSparseSet),
+    advance: Option<unsafe fn(&mut ComponentSparseSet)>,
+    debug: Option<unsafe fn(&ComponentSparseSet, usize) -> String>,
+    clone: Option<unsafe fn(&ComponentSparseSet) -> ComponentSparseSet>,
 }
 
 impl ComponentSparseSetVtable {
@@ -402,11 +540,15 @@ impl ComponentSparseSetVtable {
         T: Component,
     {
         Self {
-            grow: ComponentSparseSet::grow_typed::<T>,
+            type_data: TypeData::new::<T>(),
+            resize: ComponentSparseSet::resize_typed::<T>,
             swap: ComponentSparseSet::swap_typed::<T>,
             delete: ComponentSparseSet::delete::<T>,
             clear: ComponentSparseSet::clear_typed::<T>,
             drop: ComponentSparseSet::drop_typed::<T>,
+            advance: None,
+            debug: None,
+            clone: None,
         }
     }
 }
@@ -1,13 +1,19 @@
 use crate::entity::{
-    group, ungroup_all, Comp, CompMut, Component, ComponentSparseSet, Entity, Group, GroupInfo,
-    GroupLayout, GroupMask, GroupMetadata, QueryMask, StorageMask,
+    group, ungroup_all, BorrowError, Comp, CompMut, Component, ComponentInfo, ComponentInspection,
+    ComponentSparseSet, Drain, Entity, Group, GroupInfo, GroupLayout, GroupMask, GroupMetadata,
+    QueryMask, SnapshotError, StorageMask, UnregisteredComponentError,
 };
+use crate::system::SystemParamKind;
+use crate::util::{AccessTrace, TypeData};
 use atomic_refcell::AtomicRefCell;
 use rustc_hash::FxHashMap;
 use std::any::{self, TypeId};
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::mem;
 
+pub(crate) type FamilyGroupLens = FxHashMap<(Vec<TypeId>, Vec<usize>), Vec<usize>>;
+
 #[derive(Default, Debug)]
 pub(crate) struct ComponentStorage {
     pub(crate) groups: Vec<Group>,
@@ -21,19 +27,33 @@ impl ComponentStorage {
         entities: &[Entity],
         layout: &GroupLayout,
         mut sparse_sets: FxHashMap<TypeId, ComponentSparseSet>,
+        family_group_lens: FamilyGroupLens,
     ) -> Self {
         let mut groups = Vec::new();
         let mut metadata = FxHashMap::default();
         let mut components = Vec::new();
+        let mut group_mask = GroupMask::EMPTY;
 
         for family in layout.families() {
             let storage_start = components.len();
             let group_start = groups.len();
             let group_end = group_start + family.arities().len();
 
+            // Families that were already grouped the same way keep their groups.
+            let family_key = (
+                family.components().iter().map(TypeData::type_id).collect(),
+                family.arities().to_vec(),
+            );
+
+            let group_lens = family_group_lens.get(&family_key);
+
+            if group_lens.is_none() {
+                group_mask |= GroupMask::from_to(group_start, group_end);
+            }
+
             let mut prev_arity = 0;
 
-            for &arity in family.arities() {
+            for (arity_index, &arity) in family.arities().iter().enumerate() {
                 let storage_end = storage_start + arity;
                 let new_group_start = groups.len();
 
@@ -46,7 +66,7 @@ impl ComponentStorage {
                         include_mask: QueryMask::include(arity),
                         exclude_mask: QueryMask::exclude(prev_arity, arity),
                     },
-                    len: 0,
+                    len: group_lens.map_or(0, |group_lens| group_lens[arity_index]),
                 });
 
                 for local_storage_index in prev_arity..arity {
@@ -89,11 +109,11 @@ impl ComponentStorage {
             components.push(AtomicRefCell::new(sparse_set));
         }
 
-        let group_mask = GroupMask::from_to(0, groups.len());
-
-        for &entity in entities {
-            unsafe {
-                group(&mut components, &mut groups, group_mask, entity);
+        if group_mask != GroupMask::EMPTY {
+            for &entity in entities {
+                unsafe {
+                    group(&mut components, &mut groups, group_mask, entity);
+                }
             }
         }
 
@@ -104,7 +124,36 @@ impl ComponentStorage {
         }
     }
 
-    pub fn into_sparse_sets(mut self) -> FxHashMap<TypeId, ComponentSparseSet> {
+    pub fn into_parts(mut self) -> (FxHashMap<TypeId, ComponentSparseSet>, FamilyGroupLens) {
+        let mut family_group_lens = FamilyGroupLens::default();
+        let mut group_index = 0;
+
+        while let Some(first_group) = self.groups.get(group_index) {
+            let storage_start = first_group.metadata.storage_start;
+
+            let family_groups = self.groups[group_index..]
+                .iter()
+                .take_while(|group| group.metadata.storage_start == storage_start)
+                .collect::<Vec<_>>();
+
+            let storage_end = family_groups.last().unwrap().metadata.storage_end;
+
+            let type_ids = self.components[storage_start..storage_end]
+                .iter_mut()
+                .map(|sparse_set| sparse_set.get_mut().type_data().type_id())
+                .collect();
+
+            let arities = family_groups
+                .iter()
+                .map(|group| group.metadata.storage_end - storage_start)
+                .collect();
+
+            let group_lens = family_groups.iter().map(|group| group.len).collect();
+
+            group_index += family_groups.len();
+            family_group_lens.insert((type_ids, arities), group_lens);
+        }
+
         let mut sparse_sets = FxHashMap::default();
 
         for (type_id, metadata) in self.metadata {
@@ -116,14 +165,18 @@ impl ComponentStorage {
             sparse_sets.insert(type_id, sparse_set);
         }
 
-        sparse_sets
+        (sparse_sets, family_group_lens)
     }
 
     pub fn register<T>(&mut self) -> bool
     where
         T: Component,
     {
-        let Entry::Vacant(entry) = self.metadata.entry(TypeId::of::<T>()) else {
+        self.register_dyn(TypeData::new::<T>())
+    }
+
+    pub fn register_dyn(&mut self, type_data: TypeData) -> bool {
+        let Entry::Vacant(entry) = self.metadata.entry(type_data.type_id()) else {
             return false;
         };
 
@@ -136,11 +189,34 @@ impl ComponentStorage {
         });
 
         self.components
-            .push(AtomicRefCell::new(ComponentSparseSet::new::<T>()));
+            .push(AtomicRefCell::new(type_data.create_sparse_set()));
 
         true
     }
 
+    #[must_use]
+    pub fn get_sparse_set_mut<T>(&mut self) -> Option<&mut ComponentSparseSet>
+    where
+        T: Component,
+    {
+        let metadata = self.metadata.get(&TypeId::of::<T>())?;
+        Some(self.components[metadata.storage_index].get_mut())
+    }
+
+    #[must_use]
+    pub fn drain<T>(&mut self) -> Option<Drain<'_, T>>
+    where
+        T: Component,
+    {
+        let metadata = self.metadata.get(&TypeId::of::<T>())?;
+
+        Some(Drain::new(
+            self.components[metadata.storage_index].get_mut(),
+            &mut self.groups,
+            metadata.delete_mask,
+        ))
+    }
+
     #[must_use]
     pub fn is_registered<T>(&self) -> bool
     where
@@ -149,6 +225,22 @@ impl ComponentStorage {
         self.metadata.contains_key(&TypeId::of::<T>())
     }
 
+    #[must_use]
+    pub fn is_registered_dyn(&self, component: &TypeData) -> bool {
+        self.metadata.contains_key(&component.type_id())
+    }
+
+    #[must_use]
+    pub fn contains_dyn(&self, entity: Entity, component: &TypeData) -> bool {
+        self.metadata
+            .get(&component.type_id())
+            .is_some_and(|metadata| {
+                self.components[metadata.storage_index]
+                    .borrow()
+                    .contains(entity)
+            })
+    }
+
     pub fn strip(&mut self, entity: Entity) {
         unsafe {
             ungroup_all(&mut self.components, &mut self.groups, entity);
@@ -159,6 +251,92 @@ impl ComponentStorage {
         }
     }
 
+    pub fn try_clone(&self) -> Result<Self, SnapshotError> {
+        let components = self
+            .components
+            .iter()
+            .map(|sparse_set| {
+                let sparse_set = sparse_set.borrow();
+
+                sparse_set
+                    .try_clone()
+                    .map(AtomicRefCell::new)
+                    .ok_or_else(|| SnapshotError::new(sparse_set.type_data()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            groups: self.groups.clone(),
+            metadata: self.metadata.clone(),
+            components,
+        })
+    }
+
+    pub fn infos(&self) -> impl Iterator<Item = ComponentInfo> + '_ {
+        self.metadata.values().map(|metadata| {
+            let sparse_set = unsafe { self.components.get_unchecked(metadata.storage_index) };
+            let sparse_set = sparse_set.borrow();
+
+            ComponentInfo::new(
+                sparse_set.type_data(),
+                sparse_set.len(),
+                metadata.storage_mask.0 != 0,
+            )
+        })
+    }
+
+    #[must_use]
+    pub fn inspect(&self, entity: Entity) -> Vec<ComponentInspection> {
+        self.components
+            .iter()
+            .map(AtomicRefCell::borrow)
+            .filter(|sparse_set| sparse_set.contains(entity))
+            .map(|sparse_set| {
+                ComponentInspection::new(sparse_set.type_data(), sparse_set.debug(entity))
+            })
+            .collect()
+    }
+
+    #[must_use]
+    pub fn histogram(&self, entities: &[Entity]) -> BTreeMap<usize, usize> {
+        let sparse_sets = self
+            .components
+            .iter()
+            .map(AtomicRefCell::borrow)
+            .collect::<Vec<_>>();
+
+        let mut histogram = BTreeMap::new();
+
+        for &entity in entities {
+            let count = sparse_sets
+                .iter()
+                .filter(|sparse_set| sparse_set.contains(entity))
+                .count();
+
+            *histogram.entry(count).or_insert(0) += 1;
+        }
+
+        histogram
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        for sparse_set in &mut self.components {
+            sparse_set.get_mut().reserve(additional);
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        for sparse_set in &mut self.components {
+            sparse_set.get_mut().shrink_to_fit();
+        }
+    }
+
+    pub fn advance(&mut self) {
+        for sparse_set in &mut self.components {
+            sparse_set.get_mut().advance();
+        }
+    }
+
     pub fn clear(&mut self) {
         for group in &mut self.groups {
             group.len = 0;
@@ -170,7 +348,7 @@ impl ComponentStorage {
     }
 
     #[must_use]
-    pub fn borrow<T>(&self) -> Comp<T>
+    pub fn borrow<T>(&self) -> Comp<'_, T>
     where
         T: Component,
     {
@@ -185,18 +363,18 @@ impl ComponentStorage {
             )
         });
 
-        unsafe {
-            Comp::new(
-                self.components
-                    .get_unchecked(metadata.storage_index)
-                    .borrow(),
-                group_info,
-            )
-        }
+        let components = unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .borrow()
+        };
+
+        let trace = AccessTrace::new(SystemParamKind::Comp(TypeData::new::<T>()));
+        unsafe { Comp::new(components, group_info, trace) }
     }
 
     #[must_use]
-    pub fn borrow_mut<T>(&self) -> CompMut<T>
+    pub fn borrow_mut<T>(&self) -> CompMut<'_, T>
     where
         T: Component,
     {
@@ -211,14 +389,72 @@ impl ComponentStorage {
             )
         });
 
-        unsafe {
-            CompMut::new(
-                self.components
-                    .get_unchecked(metadata.storage_index)
-                    .borrow_mut(),
-                group_info,
+        let components = unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .borrow_mut()
+        };
+
+        let trace = AccessTrace::new(SystemParamKind::CompMut(TypeData::new::<T>()));
+        unsafe { CompMut::new(components, group_info, trace) }
+    }
+
+    pub fn try_borrow<T>(&self) -> Result<Comp<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        let type_data = TypeData::new::<T>();
+
+        let metadata = self
+            .metadata
+            .get(&TypeId::of::<T>())
+            .ok_or(UnregisteredComponentError::new(type_data))?;
+
+        let group_info = (metadata.storage_mask.0 != 0).then(|| unsafe {
+            GroupInfo::new(
+                self.groups.get_unchecked(0..metadata.group_end),
+                metadata.storage_mask,
             )
-        }
+        });
+
+        let components = unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .try_borrow()
+                .map_err(|_| BorrowError::AlreadyBorrowed(type_data))?
+        };
+
+        let trace = AccessTrace::new(SystemParamKind::Comp(type_data));
+        unsafe { Ok(Comp::new(components, group_info, trace)) }
+    }
+
+    pub fn try_borrow_mut<T>(&self) -> Result<CompMut<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        let type_data = TypeData::new::<T>();
+
+        let metadata = self
+            .metadata
+            .get(&TypeId::of::<T>())
+            .ok_or(UnregisteredComponentError::new(type_data))?;
+
+        let group_info = (metadata.storage_mask.0 != 0).then(|| unsafe {
+            GroupInfo::new(
+                self.groups.get_unchecked(0..metadata.group_end),
+                metadata.storage_mask,
+            )
+        });
+
+        let components = unsafe {
+            self.components
+                .get_unchecked(metadata.storage_index)
+                .try_borrow_mut()
+                .map_err(|_| BorrowError::AlreadyBorrowed(type_data))?
+        };
+
+        let trace = AccessTrace::new(SystemParamKind::CompMut(type_data));
+        unsafe { Ok(CompMut::new(components, group_info, trace)) }
     }
 }
 
@@ -0,0 +1,43 @@
+use crate::entity::Entity;
+use std::fmt;
+
+type DestroyHook = Box<dyn FnMut(Entity) + Send + Sync>;
+
+/// Callbacks run for every entity removed from an `EntityStorage`.
+#[derive(Default)]
+pub(crate) struct DestroyHooks {
+    hooks: Vec<DestroyHook>,
+}
+
+impl DestroyHooks {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { hooks: Vec::new() }
+    }
+
+    #[inline]
+    pub fn push(&mut self, hook: DestroyHook) {
+        self.hooks.push(hook);
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub fn run(&mut self, entity: Entity) {
+        for hook in &mut self.hooks {
+            hook(entity);
+        }
+    }
+}
+
+impl fmt::Debug for DestroyHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DestroyHooks")
+            .field("len", &self.hooks.len())
+            .finish()
+    }
+}
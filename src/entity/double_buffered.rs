@@ -0,0 +1,79 @@
+use crate::entity::{Comp, CompMut, Component, Entity};
+
+/// Component wrapper that keeps the value from the previous frame.
+///
+/// Components of type `DoubleBuffered<T>` must be registered with
+/// [`register_double_buffered`](crate::entity::EntityStorage::register_double_buffered). The
+/// previous value is then overwritten with a clone of the current value on each call to
+/// [`maintain`](crate::entity::EntityStorage::maintain).
+///
+/// Each component stores two values of type `T`, doubling the memory used by the storage.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct DoubleBuffered<T> {
+    current: T,
+    previous: T,
+}
+
+impl<T> DoubleBuffered<T>
+where
+    T: Clone,
+{
+    /// Creates a new double-buffered component where both values are equal to `value`.
+    #[inline]
+    #[must_use]
+    pub fn new(value: T) -> Self {
+        Self {
+            current: value.clone(),
+            previous: value,
+        }
+    }
+
+    /// Overwrites the previous value with a clone of the current value.
+    #[inline]
+    pub fn advance(&mut self) {
+        self.previous.clone_from(&self.current);
+    }
+}
+
+impl<T> DoubleBuffered<T> {
+    /// Returns a reference to the current value.
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// Returns a mutable reference to the current value.
+    #[inline]
+    #[must_use]
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.current
+    }
+
+    /// Returns a reference to the value from the previous frame.
+    #[inline]
+    #[must_use]
+    pub fn previous(&self) -> &T {
+        &self.previous
+    }
+}
+
+macro_rules! impl_comp_double_buffered {
+    ($Comp:ident) => {
+        impl<T> $Comp<'_, DoubleBuffered<T>>
+        where
+            T: Component,
+        {
+            /// Returns the current and previous values of the component mapped to `entity`, if
+            /// it exists.
+            #[must_use]
+            pub fn get_with_previous(&self, entity: Entity) -> Option<(&T, &T)> {
+                self.get(entity)
+                    .map(|component| (&component.current, &component.previous))
+            }
+        }
+    };
+}
+
+impl_comp_double_buffered!(Comp);
+impl_comp_double_buffered!(CompMut);
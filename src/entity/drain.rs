@@ -0,0 +1,75 @@
+use crate::entity::{Component, ComponentSparseSet, Entity, Group, GroupMask};
+use std::fmt;
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+
+/// Iterator that removes and yields all components of type `T` from the storage.
+///
+/// Created with [`EntityStorage::drain`](crate::entity::EntityStorage::drain). Components are
+/// removed as they are yielded. Dropping the iterator early leaves the remaining components in
+/// place.
+pub struct Drain<'a, T> {
+    components: &'a mut ComponentSparseSet,
+    groups: &'a mut [Group],
+    group_mask: GroupMask,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T> Drain<'a, T> {
+    #[must_use]
+    pub(crate) fn new(
+        components: &'a mut ComponentSparseSet,
+        groups: &'a mut [Group],
+        group_mask: GroupMask,
+    ) -> Self {
+        Self {
+            components,
+            groups,
+            group_mask,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Drain<'_, T>
+where
+    T: Component,
+{
+    type Item = (Entity, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Components are removed from the back so grouped entities stay at the front.
+        let entity = *self.components.entities().last()?;
+        let component = unsafe { self.components.remove::<T>(entity).unwrap_unchecked() };
+
+        // Grouped entities are a prefix of every sparse set in the group, so truncating the
+        // groups to the remaining components keeps them valid. This is done on every step
+        // so that leaking the iterator cannot leave the groups out of bounds.
+        let len = self.components.len();
+
+        for group_index in self.group_mask.iter_bit_indexes() {
+            let group = unsafe { self.groups.get_unchecked_mut(group_index as usize) };
+            group.len = group.len.min(len);
+        }
+
+        Some((entity, component))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.components.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> where T: Component {}
+
+impl<T> FusedIterator for Drain<'_, T> where T: Component {}
+
+impl<T> fmt::Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Drain")
+            .field("remaining", &self.components.len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,49 @@
+use crate::entity::Entity;
+use std::iter::{FusedIterator, Zip};
+use std::ops::Range;
+
+/// Iterator over the entities in an [`Entities`](crate::entity::Entities) view.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct EntitiesIter<'a> {
+    entities: &'a [Entity],
+    index: usize,
+}
+
+impl<'a> EntitiesIter<'a> {
+    #[inline]
+    pub(crate) fn new(entities: &'a [Entity]) -> Self {
+        Self { entities, index: 0 }
+    }
+
+    /// Makes the iterator also return the dense index of each entity in the storage.
+    #[inline]
+    pub fn with_index(self) -> Zip<Range<usize>, Self> {
+        (self.index..self.entities.len()).zip(self)
+    }
+}
+
+impl Iterator for EntitiesIter<'_> {
+    type Item = Entity;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = *self.entities.get(self.index)?;
+        self.index += 1;
+        Some(entity)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.entities.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for EntitiesIter<'_> {
+    // Empty
+}
+
+impl FusedIterator for EntitiesIter<'_> {
+    // Empty
+}
@@ -1,5 +1,6 @@
-use crate::entity::Entity;
+use crate::entity::{Entity, RecyclePolicy};
 use std::collections::VecDeque;
+use std::mem;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 #[derive(Default, Debug)]
@@ -8,6 +9,11 @@ pub(crate) struct EntityAllocator {
     last_maintained_index: u64,
     recycled: VecDeque<Entity>,
     recycled_since_maintain: AtomicUsize,
+    allocated_since_maintain: Vec<u32>,
+    policy: RecyclePolicy,
+    pending: VecDeque<(u32, Entity)>,
+    limit: Option<usize>,
+    over_limit: Vec<Entity>,
 }
 
 impl EntityAllocator {
@@ -19,6 +25,11 @@ impl EntityAllocator {
             last_maintained_index: 0,
             recycled: VecDeque::new(),
             recycled_since_maintain: AtomicUsize::new(0),
+            allocated_since_maintain: Vec::new(),
+            policy: RecyclePolicy::Immediate,
+            pending: VecDeque::new(),
+            limit: None,
+            over_limit: Vec::new(),
         }
     }
 
@@ -27,10 +38,22 @@ impl EntityAllocator {
         let recycled_since_maintain = *self.recycled_since_maintain.get_mut();
 
         if recycled_since_maintain < self.recycled.len() {
-            *self.recycled_since_maintain.get_mut() += 1;
-            Some(self.recycled[self.recycled.len() - recycled_since_maintain - 1])
-        } else if let Ok(index) = u32::try_from(*self.next_index_to_allocate.get_mut()) {
-            *self.next_index_to_allocate.get_mut() += 1;
+            // Entities added to the storage right away must not be returned by `maintain`.
+            // Those allocated atomically are at the back of the queue and stay there.
+            self.recycled
+                .remove(self.recycled.len() - recycled_since_maintain - 1)
+        } else if let Some(index) =
+            index_within_limit(*self.next_index_to_allocate.get_mut(), self.limit)
+        {
+            let next_index_to_allocate = self.next_index_to_allocate.get_mut();
+
+            if self.last_maintained_index == *next_index_to_allocate {
+                self.last_maintained_index += 1;
+            } else {
+                self.allocated_since_maintain.push(index);
+            }
+
+            *next_index_to_allocate += 1;
             Some(Entity::with_index(index))
         } else {
             None
@@ -50,14 +73,74 @@ impl EntityAllocator {
         }
     }
 
+    #[inline]
+    #[must_use]
+    pub fn recycle_policy(&self) -> RecyclePolicy {
+        self.policy
+    }
+
+    #[inline]
+    pub fn set_recycle_policy(&mut self, policy: RecyclePolicy) {
+        self.policy = policy;
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+
+        // Entities allocated atomically since the last maintain must stay at the back of the queue.
+        let available = self.recycled.len() - *self.recycled_since_maintain.get_mut();
+        let allocated = self.recycled.split_off(available);
+
+        let (recycled, over_limit): (Vec<_>, Vec<_>) = self
+            .recycled
+            .drain(..)
+            .chain(self.over_limit.drain(..))
+            .partition(|&entity| entity_within_limit(entity, limit));
+
+        self.recycled = VecDeque::from(recycled);
+        self.recycled.extend(allocated);
+        self.over_limit = over_limit;
+    }
+
     pub fn recycle(&mut self, entity: Entity) {
-        if let Some(next_version) = entity.version.next() {
-            self.recycled
-                .push_front(Entity::new(entity.index, next_version));
+        let Some(next_version) = entity.version.next() else {
+            return;
+        };
+
+        let entity = Entity::new(entity.index, next_version);
+
+        match self.policy {
+            RecyclePolicy::Immediate | RecyclePolicy::Deferred(0) => {
+                push_recycled(&mut self.recycled, &mut self.over_limit, self.limit, entity);
+            }
+            RecyclePolicy::Deferred(frames) => {
+                self.pending.push_back((frames, entity));
+            }
         }
     }
 
     pub fn maintain(&mut self) -> impl Iterator<Item = Entity> + '_ {
+        let recycled = &mut self.recycled;
+        let over_limit = &mut self.over_limit;
+        let limit = self.limit;
+
+        self.pending.retain_mut(|(frames, entity)| {
+            *frames -= 1;
+
+            if *frames == 0 {
+                push_recycled(recycled, over_limit, limit, *entity);
+                false
+            } else {
+                true
+            }
+        });
+
         let recycled_range = {
             let recycled_since_maintain = *self.recycled_since_maintain.get_mut();
             *self.recycled_since_maintain.get_mut() = 0;
@@ -71,9 +154,15 @@ impl EntityAllocator {
             new_index_range
         };
 
-        self.recycled
-            .drain(recycled_range)
-            .chain(new_index_range.map(|i| Entity::with_index(i as u32)))
+        let mut allocated_since_maintain = mem::take(&mut self.allocated_since_maintain);
+        allocated_since_maintain.sort_unstable();
+
+        self.recycled.drain(recycled_range).chain(
+            new_index_range
+                .map(|i| i as u32)
+                .filter(move |i| allocated_since_maintain.binary_search(i).is_err())
+                .map(Entity::with_index),
+        )
     }
 
     pub fn reset(&mut self) {
@@ -81,6 +170,15 @@ impl EntityAllocator {
         self.last_maintained_index = 0;
         self.recycled.clear();
         *self.recycled_since_maintain.get_mut() = 0;
+        self.allocated_since_maintain.clear();
+        self.pending.clear();
+        self.over_limit.clear();
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.recycled.shrink_to_fit();
+        self.pending.shrink_to_fit();
+        self.over_limit.shrink_to_fit();
     }
 
     fn increment_recycled_since_maintain(&self) -> Option<usize> {
@@ -105,7 +203,7 @@ impl EntityAllocator {
     fn increment_next_index_to_allocate(&self) -> Option<u32> {
         let mut prev = self.next_index_to_allocate.load(Ordering::Relaxed);
 
-        while u32::try_from(prev).is_ok() {
+        while index_within_limit(prev, self.limit).is_some() {
             match self.next_index_to_allocate.compare_exchange_weak(
                 prev,
                 prev + 1,
@@ -120,3 +218,47 @@ impl EntityAllocator {
         None
     }
 }
+
+fn index_within_limit(index: u64, limit: Option<usize>) -> Option<u32> {
+    let index = u32::try_from(index).ok()?;
+    let within_limit = limit.is_none_or(|limit| (index as usize) < limit);
+    within_limit.then_some(index)
+}
+
+fn entity_within_limit(entity: Entity, limit: Option<usize>) -> bool {
+    limit.is_none_or(|limit| entity.sparse() < limit)
+}
+
+/// Recycled entities whose index is not below the limit are kept aside until the limit is raised.
+fn push_recycled(
+    recycled: &mut VecDeque<Entity>,
+    over_limit: &mut Vec<Entity>,
+    limit: Option<usize>,
+    entity: Entity,
+) {
+    if entity_within_limit(entity, limit) {
+        recycled.push_front(entity);
+    } else {
+        over_limit.push(entity);
+    }
+}
+
+impl Clone for EntityAllocator {
+    fn clone(&self) -> Self {
+        Self {
+            next_index_to_allocate: AtomicU64::new(
+                self.next_index_to_allocate.load(Ordering::Relaxed),
+            ),
+            last_maintained_index: self.last_maintained_index,
+            recycled: self.recycled.clone(),
+            recycled_since_maintain: AtomicUsize::new(
+                self.recycled_since_maintain.load(Ordering::Relaxed),
+            ),
+            allocated_since_maintain: self.allocated_since_maintain.clone(),
+            policy: self.policy,
+            pending: self.pending.clone(),
+            limit: self.limit,
+            over_limit: self.over_limit.clone(),
+        }
+    }
+}
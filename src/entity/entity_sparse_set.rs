@@ -81,6 +81,23 @@ impl EntitySparseSet {
         &self.entities
     }
 
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.sparse.shrink_to_fit();
+        self.entities.shrink_to_fit();
+    }
+
     #[inline]
     pub fn clear(&mut self) {
         self.sparse.clear();
@@ -0,0 +1,160 @@
+use crate::util::TypeData;
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when trying to use a component type that was not registered.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct UnregisteredComponentError {
+    type_data: TypeData,
+}
+
+impl UnregisteredComponentError {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(type_data: TypeData) -> Self {
+        Self { type_data }
+    }
+
+    /// Returns the type data of the unregistered component.
+    #[inline]
+    #[must_use]
+    pub const fn type_data(&self) -> TypeData {
+        self.type_data
+    }
+
+    /// Returns the type name of the unregistered component.
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_data.type_name()
+    }
+}
+
+impl fmt::Display for UnregisteredComponentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Component '{}' was not registered", self.type_name())
+    }
+}
+
+impl Error for UnregisteredComponentError {
+    // Empty
+}
+
+/// Error returned when trying to snapshot a storage that contains a component type which cannot
+/// be cloned.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct SnapshotError {
+    type_data: TypeData,
+}
+
+impl SnapshotError {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(type_data: TypeData) -> Self {
+        Self { type_data }
+    }
+
+    /// Returns the type data of the component that cannot be cloned.
+    #[inline]
+    #[must_use]
+    pub const fn type_data(&self) -> TypeData {
+        self.type_data
+    }
+
+    /// Returns the type name of the component that cannot be cloned.
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_data.type_name()
+    }
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Component '{}' cannot be cloned, register it with `register_cloneable`",
+            self.type_name(),
+        )
+    }
+}
+
+impl Error for SnapshotError {
+    // Empty
+}
+
+/// Error returned when trying to borrow a view over a component type.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BorrowError {
+    /// The component type was not registered.
+    Unregistered(UnregisteredComponentError),
+    /// The component storage is already borrowed in a conflicting way.
+    AlreadyBorrowed(TypeData),
+}
+
+impl BorrowError {
+    /// Returns the type data of the component that could not be borrowed.
+    #[inline]
+    #[must_use]
+    pub const fn type_data(&self) -> TypeData {
+        match self {
+            Self::Unregistered(error) => error.type_data(),
+            Self::AlreadyBorrowed(type_data) => *type_data,
+        }
+    }
+
+    /// Returns the type name of the component that could not be borrowed.
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_data().type_name()
+    }
+}
+
+impl From<UnregisteredComponentError> for BorrowError {
+    #[inline]
+    fn from(error: UnregisteredComponentError) -> Self {
+        Self::Unregistered(error)
+    }
+}
+
+impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unregistered(error) => error.fmt(f),
+            Self::AlreadyBorrowed(type_data) => {
+                write!(
+                    f,
+                    "Component '{}' is already borrowed",
+                    type_data.type_name()
+                )
+            }
+        }
+    }
+}
+
+impl Error for BorrowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unregistered(error) => Some(error),
+            Self::AlreadyBorrowed(_) => None,
+        }
+    }
+}
+
+/// Error returned when trying to create an entity after the entity limit was reached.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct EntityLimitError;
+
+impl fmt::Display for EntityLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to create a new Entity, the entity limit was reached"
+        )
+    }
+}
+
+impl Error for EntityLimitError {
+    // Empty
+}
@@ -0,0 +1,123 @@
+use crate::entity::Entity;
+use crate::util::TypeData;
+
+/// Snapshot of the components of an entity, used for debugging.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EntityInspection {
+    entity: Entity,
+    components: Vec<ComponentInspection>,
+}
+
+impl EntityInspection {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(entity: Entity, components: Vec<ComponentInspection>) -> Self {
+        Self { entity, components }
+    }
+
+    /// Returns the inspected entity.
+    #[inline]
+    #[must_use]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Returns the components of the entity, in no particular order.
+    #[inline]
+    #[must_use]
+    pub fn components(&self) -> &[ComponentInspection] {
+        &self.components
+    }
+}
+
+/// Snapshot of a single component, used for debugging.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ComponentInspection {
+    type_data: TypeData,
+    debug: Option<String>,
+}
+
+impl ComponentInspection {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(type_data: TypeData, debug: Option<String>) -> Self {
+        Self { type_data, debug }
+    }
+
+    /// Returns the type data of the component.
+    #[inline]
+    #[must_use]
+    pub fn type_data(&self) -> TypeData {
+        self.type_data
+    }
+
+    /// Returns the type name of the component.
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_data.type_name()
+    }
+
+    /// Returns the `Debug` representation of the component, if the component was registered with
+    /// [`register_debug`](crate::entity::EntityStorage::register_debug).
+    #[inline]
+    #[must_use]
+    pub fn debug(&self) -> Option<&str> {
+        self.debug.as_deref()
+    }
+}
+
+/// Information about a registered component type.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ComponentInfo {
+    type_data: TypeData,
+    len: usize,
+    is_grouped: bool,
+}
+
+impl ComponentInfo {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(type_data: TypeData, len: usize, is_grouped: bool) -> Self {
+        Self {
+            type_data,
+            len,
+            is_grouped,
+        }
+    }
+
+    /// Returns the type data of the component.
+    #[inline]
+    #[must_use]
+    pub const fn type_data(&self) -> TypeData {
+        self.type_data
+    }
+
+    /// Returns the type name of the component.
+    #[inline]
+    #[must_use]
+    pub fn type_name(&self) -> &'static str {
+        self.type_data.type_name()
+    }
+
+    /// Returns the number of entities that have the component.
+    #[inline]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no entities have the component.
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns whether the component is part of a group in the current `GroupLayout`.
+    #[inline]
+    #[must_use]
+    pub const fn is_grouped(&self) -> bool {
+        self.is_grouped
+    }
+}
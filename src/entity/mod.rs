@@ -5,32 +5,53 @@ mod component;
 mod component_set;
 mod component_sparse_set;
 mod component_storage;
+mod destroy_hooks;
+mod double_buffered;
+mod drain;
+mod entities_iter;
 mod entity;
 mod entity_allocator;
 mod entity_sparse_set;
+mod error;
 mod group;
 mod group_info;
 mod group_layout;
 mod group_mask;
+mod inspection;
+mod other_components;
+mod recycle_policy;
+mod sample;
+mod snapshot;
 mod sparse_vec;
 
 pub use self::borrow::*;
 pub use self::component::*;
 pub use self::component_set::*;
+pub use self::double_buffered::*;
+pub use self::drain::*;
+pub use self::entities_iter::*;
 pub use self::entity::*;
+pub use self::error::*;
 pub use self::group_info::*;
 pub use self::group_layout::*;
+pub use self::inspection::*;
+pub use self::other_components::*;
+pub use self::recycle_policy::*;
+pub use self::sample::*;
+pub use self::snapshot::*;
 pub use self::sparse_vec::*;
 
 pub(crate) use self::component_sparse_set::*;
 pub(crate) use self::component_storage::*;
+pub(crate) use self::destroy_hooks::*;
 pub(crate) use self::entity_allocator::*;
 pub(crate) use self::entity_sparse_set::*;
 pub(crate) use self::group::*;
 pub(crate) use self::group_mask::*;
 
 use rustc_hash::FxHashMap;
-use std::mem;
+use std::collections::BTreeMap;
+use std::{fmt, mem};
 
 /// Storage for entities and components.
 #[derive(Default, Debug)]
@@ -38,6 +59,7 @@ pub struct EntityStorage {
     allocator: EntityAllocator,
     entities: EntitySparseSet,
     components: ComponentStorage,
+    destroy_hooks: DestroyHooks,
 }
 
 impl EntityStorage {
@@ -45,25 +67,83 @@ impl EntityStorage {
     #[inline]
     #[must_use]
     pub fn new(layout: &GroupLayout) -> Self {
-        let components = unsafe { ComponentStorage::new(&[], layout, FxHashMap::default()) };
+        let components = unsafe {
+            ComponentStorage::new(&[], layout, FxHashMap::default(), FxHashMap::default())
+        };
 
         Self {
             allocator: EntityAllocator::new(),
             entities: EntitySparseSet::new(),
             components,
+            destroy_hooks: DestroyHooks::new(),
         }
     }
 
     /// Sets a new `GroupLayout`.
     ///
-    /// This function iterates over all entities in the storage, so it is best called when the
-    /// storage is empty.
+    /// Group families that are unchanged from the previous layout keep their groups. For the
+    /// other families, this function iterates over all entities in the storage, so it is best
+    /// called when the storage is empty.
     #[inline]
     pub fn set_layout(&mut self, layout: &GroupLayout) {
-        let sparse_sets = mem::take(&mut self.components).into_sparse_sets();
+        let (sparse_sets, family_group_lens) = mem::take(&mut self.components).into_parts();
 
-        self.components =
-            unsafe { ComponentStorage::new(self.entities.as_slice(), layout, sparse_sets) };
+        self.components = unsafe {
+            ComponentStorage::new(
+                self.entities.as_slice(),
+                layout,
+                sparse_sets,
+                family_group_lens,
+            )
+        };
+    }
+
+    /// Returns the policy used to recycle the indexes of destroyed entities.
+    #[inline]
+    #[must_use]
+    pub fn recycle_policy(&self) -> RecyclePolicy {
+        self.allocator.recycle_policy()
+    }
+
+    /// Sets the policy used to recycle the indexes of destroyed entities. Only affects entities
+    /// destroyed after this call.
+    #[inline]
+    pub fn set_recycle_policy(&mut self, policy: RecyclePolicy) {
+        self.allocator.set_recycle_policy(policy);
+    }
+
+    /// Returns the maximum number of entity indexes the storage can allocate, if any.
+    #[inline]
+    #[must_use]
+    pub fn entity_limit(&self) -> Option<usize> {
+        self.allocator.limit()
+    }
+
+    /// Sets the maximum number of entity indexes the storage can allocate. New entities,
+    /// including those that reuse the index of a destroyed entity, always get an index below
+    /// `limit`.
+    ///
+    /// Once the limit is reached, [`create`](Self::create) panics and
+    /// [`checked_create`](Self::checked_create) returns an error until an entity is recycled.
+    /// Lowering the limit does not destroy existing entities, so more than `limit` entities may
+    /// exist until those with higher indexes are destroyed.
+    #[inline]
+    pub fn set_entity_limit(&mut self, limit: Option<usize>) {
+        self.allocator.set_limit(limit);
+    }
+
+    /// Adds a callback that runs for every entity removed by [`destroy`](Self::destroy),
+    /// [`destroy_all_with`](Self::destroy_all_with), [`clear`](Self::clear) or
+    /// [`reset`](Self::reset), after the entity and its components are removed.
+    ///
+    /// Useful for removing data stored outside of the storage, like in a
+    /// [`SecondaryMap`](crate::util::SecondaryMap). [`restore`](Self::restore) keeps the callbacks
+    /// but does not run them for the entities it removes.
+    pub fn on_destroy<F>(&mut self, f: F)
+    where
+        F: FnMut(Entity) + Send + Sync + 'static,
+    {
+        self.destroy_hooks.push(Box::new(f));
     }
 
     /// Registers a new component type.
@@ -76,6 +156,83 @@ impl EntityStorage {
         self.components.register::<T>()
     }
 
+    /// Registers all component types in the set `C`.
+    ///
+    /// Returns the number of newly registered components.
+    pub fn register_bundle<C>(&mut self) -> usize
+    where
+        C: ComponentSet,
+    {
+        C::COMPONENTS
+            .iter()
+            .filter(|&&component| self.components.register_dyn(component))
+            .count()
+    }
+
+    /// Registers a new component type `DoubleBuffered<T>` whose previous values are updated on
+    /// each call to [`maintain`](Self::maintain). The component can be included in a
+    /// [`snapshot`](Self::snapshot).
+    ///
+    /// Returns whether the component was newly registered.
+    pub fn register_double_buffered<T>(&mut self) -> bool
+    where
+        T: Component + Clone,
+    {
+        let registered = self.components.register::<DoubleBuffered<T>>();
+
+        unsafe {
+            let sparse_set = self
+                .components
+                .get_sparse_set_mut::<DoubleBuffered<T>>()
+                .unwrap_unchecked();
+
+            sparse_set.set_double_buffered::<T>();
+            sparse_set.set_clone::<DoubleBuffered<T>>();
+        }
+
+        registered
+    }
+
+    /// Registers a new component type `T` whose `Debug` representation is included in
+    /// [`inspect_entity`](Self::inspect_entity).
+    ///
+    /// Returns whether the component was newly registered.
+    pub fn register_debug<T>(&mut self) -> bool
+    where
+        T: Component + fmt::Debug,
+    {
+        let registered = self.components.register::<T>();
+
+        unsafe {
+            self.components
+                .get_sparse_set_mut::<T>()
+                .unwrap_unchecked()
+                .set_debug::<T>();
+        }
+
+        registered
+    }
+
+    /// Registers a new component type `T` that can be included in a
+    /// [`snapshot`](Self::snapshot).
+    ///
+    /// Returns whether the component was newly registered.
+    pub fn register_cloneable<T>(&mut self) -> bool
+    where
+        T: Component + Clone,
+    {
+        let registered = self.components.register::<T>();
+
+        unsafe {
+            self.components
+                .get_sparse_set_mut::<T>()
+                .unwrap_unchecked()
+                .set_clone::<T>();
+        }
+
+        registered
+    }
+
     /// Returns whether component type `T` is registered.
     #[must_use]
     pub fn is_registered<T>(&self) -> bool
@@ -97,6 +254,40 @@ impl EntityStorage {
         entity
     }
 
+    /// Creates a new entity with the given `components`, if all component types are registered.
+    ///
+    /// Returns the newly created entity or an error naming the first unregistered component.
+    pub fn try_create<C>(&mut self, components: C) -> Result<Entity, UnregisteredComponentError>
+    where
+        C: ComponentSet,
+    {
+        self.check_registered::<C>()?;
+        Ok(self.create(components))
+    }
+
+    /// Creates a new entity with the given `components`, if the entity limit was not reached.
+    ///
+    /// Returns the newly created entity or an error if no entity could be allocated.
+    pub fn checked_create<C>(&mut self, components: C) -> Result<Entity, EntityLimitError>
+    where
+        C: ComponentSet,
+    {
+        let entity = self.allocator.allocate().ok_or(EntityLimitError)?;
+        self.entities.insert(entity);
+        C::insert(self, entity, components);
+        Ok(entity)
+    }
+
+    /// Creates a new entity with clones of the components in `template`.
+    ///
+    /// Returns the newly created entity.
+    pub fn create_from<C>(&mut self, template: &C) -> Entity
+    where
+        C: ComponentSet + Clone,
+    {
+        self.create(template.clone())
+    }
+
     /// Creates new entities with the components produced by the iterator.
     ///
     /// Returns the newly created entities as a slice.
@@ -108,6 +299,34 @@ impl EntityStorage {
         C::extend(self, components)
     }
 
+    /// Creates `count` new entities with the components returned by calling `f` once per entity.
+    ///
+    /// Returns the newly created entities as a slice.
+    pub fn create_n<C>(&mut self, count: usize, mut f: impl FnMut() -> C) -> &[Entity]
+    where
+        C: ComponentSet,
+    {
+        C::extend(self, (0..count).map(|_| f()))
+    }
+
+    /// Creates new entities with the components produced by the parallel iterator.
+    ///
+    /// The components are produced in parallel and then added to the storage sequentially, in
+    /// the order of the iterator.
+    ///
+    /// Returns the newly created entities as a slice.
+    #[cfg(feature = "parallel")]
+    pub fn par_extend<C, I>(&mut self, components: I) -> &[Entity]
+    where
+        C: ComponentSet + Send,
+        I: rayon::iter::IntoParallelIterator<Item = C>,
+    {
+        use rayon::iter::ParallelIterator;
+
+        let components = components.into_par_iter().collect::<Vec<_>>();
+        C::extend(self, components)
+    }
+
     /// Creates a new entity without requiring exclusive access to the storage. The entity is not
     /// added to the storage until [`maintain`](Self::maintain) is called.
     ///
@@ -119,6 +338,16 @@ impl EntityStorage {
             .expect("Failed to create a new Entity")
     }
 
+    /// Creates a new entity without requiring exclusive access to the storage, if the entity
+    /// limit was not reached. The entity is not added to the storage until
+    /// [`maintain`](Self::maintain) is called.
+    ///
+    /// Returns the newly created entity or an error if no entity could be allocated.
+    #[inline]
+    pub fn checked_create_atomic(&self) -> Result<Entity, EntityLimitError> {
+        self.allocator.allocate_atomic().ok_or(EntityLimitError)
+    }
+
     /// Adds the given `components` to `entity` if `entity` is present in the storage.
     ///
     /// Returns whether the components were successfully added.
@@ -134,6 +363,23 @@ impl EntityStorage {
         true
     }
 
+    /// Adds the given `components` to `entity` if `entity` is present in the storage and all
+    /// component types are registered.
+    ///
+    /// Returns whether the components were successfully added or an error naming the first
+    /// unregistered component.
+    pub fn try_insert<C>(
+        &mut self,
+        entity: Entity,
+        components: C,
+    ) -> Result<bool, UnregisteredComponentError>
+    where
+        C: ComponentSet,
+    {
+        self.check_registered::<C>()?;
+        Ok(self.insert(entity, components))
+    }
+
     /// Removes components from the given `entity`.
     ///
     /// Returns the components that were successfully removed.
@@ -145,6 +391,84 @@ impl EntityStorage {
         C::remove(self, entity)
     }
 
+    /// Removes the component of type `T` from the given `entity`.
+    ///
+    /// Returns the removed component, if any.
+    #[must_use = "Use `delete` to discard the component."]
+    pub fn take_component<T>(&mut self, entity: Entity) -> Option<T>
+    where
+        T: Component,
+    {
+        let (component,) = self.remove::<(T,)>(entity);
+        component
+    }
+
+    /// Returns a reference to the component of type `T` mapped to `entity`, if it exists.
+    ///
+    /// Requires exclusive access to the storage to ensure the component is not mutably borrowed.
+    /// Use [`borrow`](Self::borrow) for shared access.
+    #[must_use]
+    pub fn get_component<T>(&mut self, entity: Entity) -> Option<&T>
+    where
+        T: Component,
+    {
+        self.get_component_mut(entity).map(|component| &*component)
+    }
+
+    /// Returns a mutable reference to the component of type `T` mapped to `entity`, if it
+    /// exists.
+    #[must_use]
+    pub fn get_component_mut<T>(&mut self, entity: Entity) -> Option<&mut T>
+    where
+        T: Component,
+    {
+        let sparse_set = self.components.get_sparse_set_mut::<T>()?;
+        unsafe { sparse_set.get_mut(entity) }
+    }
+
+    /// Returns a mutable reference to the component of type `T` mapped to `entity`, inserting
+    /// the value returned by `f` if the component is absent.
+    ///
+    /// Returns [`None`] if `entity` is not present in the storage. Panics if `T` is not
+    /// registered.
+    pub fn get_component_or_insert_with<T>(
+        &mut self,
+        entity: Entity,
+        f: impl FnOnce() -> T,
+    ) -> Option<&mut T>
+    where
+        T: Component,
+    {
+        if !self.entities.contains(entity) {
+            return None;
+        }
+
+        let sparse_set = self
+            .components
+            .get_sparse_set_mut::<T>()
+            .unwrap_or_else(|| panic_missing_comp::<T>());
+
+        if !sparse_set.contains(entity) {
+            <(T,)>::insert(self, entity, (f(),));
+        }
+
+        self.get_component_mut(entity)
+    }
+
+    /// Returns all entities that have a component of type `T`, in unspecified order.
+    ///
+    /// Requires exclusive access to the storage to ensure the component is not mutably borrowed.
+    /// Use [`Comp::entities`] for shared access.
+    #[must_use]
+    pub fn entities_with<T>(&mut self) -> &[Entity]
+    where
+        T: Component,
+    {
+        self.components
+            .get_sparse_set_mut::<T>()
+            .map_or(&[], |sparse_set| sparse_set.entities())
+    }
+
     /// Removes components from the given `entity`.
     pub fn delete<C>(&mut self, entity: Entity)
     where
@@ -153,6 +477,19 @@ impl EntityStorage {
         C::delete(self, entity);
     }
 
+    /// Returns an iterator that removes and yields all components of type `T` together with the
+    /// entities they were mapped to.
+    ///
+    /// Dropping the iterator early leaves the remaining components in the storage.
+    pub fn drain<T>(&mut self) -> Drain<'_, T>
+    where
+        T: Component,
+    {
+        self.components
+            .drain()
+            .unwrap_or_else(|| panic_missing_comp::<T>())
+    }
+
     /// Removes the given `entity` and its components from the storage.
     ///
     /// Returns whether the `entity` was present in the storage.
@@ -164,9 +501,30 @@ impl EntityStorage {
 
         self.allocator.recycle(entity);
         self.components.strip(entity);
+        self.destroy_hooks.run(entity);
         true
     }
 
+    /// Removes all entities that have a component of type `T`, along with all their components.
+    ///
+    /// Returns the number of destroyed entities.
+    pub fn destroy_all_with<T>(&mut self) -> usize
+    where
+        T: Component,
+    {
+        let Some(sparse_set) = self.components.get_sparse_set_mut::<T>() else {
+            return 0;
+        };
+
+        let entities = sparse_set.entities().to_vec();
+
+        for &entity in &entities {
+            self.destroy(entity);
+        }
+
+        entities.len()
+    }
+
     /// Returns whether the storage contains no entities.
     #[inline]
     #[must_use]
@@ -178,8 +536,7 @@ impl EntityStorage {
     #[inline]
     pub fn clear(&mut self) {
         self.maintain();
-        self.entities.clear();
-        self.components.clear();
+        self.clear_all_entities();
     }
 
     /// Removes all entities and components from the storage and resets the entity allocator.
@@ -188,16 +545,84 @@ impl EntityStorage {
     #[inline]
     pub fn reset(&mut self) {
         self.allocator.reset();
+        self.clear_all_entities();
+    }
+
+    fn clear_all_entities(&mut self) {
+        let entities = if self.destroy_hooks.is_empty() {
+            Vec::new()
+        } else {
+            self.entities.as_slice().to_vec()
+        };
+
         self.entities.clear();
         self.components.clear();
+
+        for entity in entities {
+            self.destroy_hooks.run(entity);
+        }
     }
 
-    /// Adds the entities allocated with [`create_atomic`](Self::create_atomic) to the storage.
+    /// Returns a copy of all entities and components in the storage, to be applied later with
+    /// [`restore`](Self::restore).
+    ///
+    /// Returns an error if any registered component type cannot be cloned. Component types must
+    /// be registered with [`register_cloneable`](Self::register_cloneable) to be cloneable.
+    /// Panics if any component storage is already borrowed mutably.
+    pub fn snapshot(&self) -> Result<EntitySnapshot, SnapshotError> {
+        Ok(EntitySnapshot {
+            storage: Self {
+                allocator: self.allocator.clone(),
+                entities: self.entities.clone(),
+                components: self.components.try_clone()?,
+                destroy_hooks: DestroyHooks::new(),
+            },
+        })
+    }
+
+    /// Replaces all entities, components and the group layout with the ones in the `snapshot`.
+    #[inline]
+    pub fn restore(&mut self, snapshot: EntitySnapshot) {
+        let destroy_hooks = mem::take(&mut self.destroy_hooks);
+        *self = snapshot.storage;
+        self.destroy_hooks = destroy_hooks;
+    }
+
+    /// Returns the number of entities the storage can hold without reallocating.
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.entities.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more entities, both in the storage and in
+    /// each registered component storage.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+        self.components.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the storage and all component storages as much as possible.
+    ///
+    /// Best called after [`clear`](Self::clear) to release the memory used by the removed
+    /// entities.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.allocator.shrink_to_fit();
+        self.entities.shrink_to_fit();
+        self.components.shrink_to_fit();
+    }
+
+    /// Adds the entities allocated with [`create_atomic`](Self::create_atomic) to the storage and
+    /// advances all [`DoubleBuffered`] components to the next frame.
     #[inline]
     pub fn maintain(&mut self) {
         self.allocator.maintain().for_each(|entity| {
             self.entities.insert(entity);
         });
+
+        self.components.advance();
     }
 
     /// Returns wether `entity` is present in the storage.
@@ -207,6 +632,68 @@ impl EntityStorage {
         self.entities.contains(entity)
     }
 
+    /// Returns whether `entity` has all components in the set `C`.
+    ///
+    /// Unregistered components are treated as absent. Panics if any component storage in `C` is
+    /// already borrowed mutably.
+    #[must_use]
+    pub fn has_components<C>(&self, entity: Entity) -> bool
+    where
+        C: ComponentSet,
+    {
+        C::COMPONENTS
+            .iter()
+            .all(|component| self.components.contains_dyn(entity, component))
+    }
+
+    /// Returns whether `entity` has at least one of the components in the set `C`.
+    ///
+    /// Unregistered components are treated as absent. Panics if any component storage in `C` is
+    /// already borrowed mutably.
+    #[must_use]
+    pub fn has_any_components<C>(&self, entity: Entity) -> bool
+    where
+        C: ComponentSet,
+    {
+        C::COMPONENTS
+            .iter()
+            .any(|component| self.components.contains_dyn(entity, component))
+    }
+
+    /// Returns an iterator over information about all registered component types, in no
+    /// particular order.
+    ///
+    /// Panics if any component storage is already borrowed mutably.
+    pub fn registered_components(&self) -> impl Iterator<Item = ComponentInfo> + '_ {
+        self.components.infos()
+    }
+
+    /// Returns a map from the number of components an entity has to the number of entities with
+    /// that many components.
+    ///
+    /// Checks every entity against every component storage, so it runs in
+    /// `O(entities * components)` time. Panics if any component storage is already borrowed
+    /// mutably.
+    #[must_use]
+    pub fn component_histogram(&self) -> BTreeMap<usize, usize> {
+        self.components.histogram(self.entities.as_slice())
+    }
+
+    /// Returns a snapshot of all components mapped to `entity`, if it exists.
+    ///
+    /// Panics if any component storage is already borrowed mutably.
+    #[must_use]
+    pub fn inspect_entity(&self, entity: Entity) -> Option<EntityInspection> {
+        if !self.entities.contains(entity) {
+            return None;
+        }
+
+        Some(EntityInspection::new(
+            entity,
+            self.components.inspect(entity),
+        ))
+    }
+
     /// Returns all entities in the storage as a slice.
     #[inline]
     #[must_use]
@@ -220,13 +707,13 @@ impl EntityStorage {
     /// storage.
     #[inline]
     #[must_use]
-    pub fn borrow_entities(&self) -> Entities {
+    pub fn borrow_entities(&self) -> Entities<'_> {
         Entities::new(self)
     }
 
     /// Borrows a shared view over all components of type `T` in the storage.
     #[must_use]
-    pub fn borrow<T>(&self) -> Comp<T>
+    pub fn borrow<T>(&self) -> Comp<'_, T>
     where
         T: Component,
     {
@@ -235,13 +722,44 @@ impl EntityStorage {
 
     /// Borrows an exclusive view over all components of type `T` in the storage.
     #[must_use]
-    pub fn borrow_mut<T>(&self) -> CompMut<T>
+    pub fn borrow_mut<T>(&self) -> CompMut<'_, T>
     where
         T: Component,
     {
         self.components.borrow_mut::<T>()
     }
 
+    /// Borrows a shared view over all components of type `T` in the storage, if the component
+    /// type is registered and not already borrowed mutably.
+    pub fn try_borrow<T>(&self) -> Result<Comp<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        self.components.try_borrow::<T>()
+    }
+
+    /// Borrows an exclusive view over all components of type `T` in the storage, if the
+    /// component type is registered and not already borrowed.
+    pub fn try_borrow_mut<T>(&self) -> Result<CompMut<'_, T>, BorrowError>
+    where
+        T: Component,
+    {
+        self.components.try_borrow_mut::<T>()
+    }
+
+    fn check_registered<C>(&self) -> Result<(), UnregisteredComponentError>
+    where
+        C: ComponentSet,
+    {
+        match C::COMPONENTS
+            .iter()
+            .find(|component| !self.components.is_registered_dyn(component))
+        {
+            Some(&component) => Err(UnregisteredComponentError::new(component)),
+            None => Ok(()),
+        }
+    }
+
     #[inline]
     #[must_use]
     fn create_empty_entity(&mut self) -> Entity {
@@ -0,0 +1,76 @@
+use crate::entity::{Entity, SparseVec};
+
+/// Shared view over all components of type `T` except the one currently being mutated.
+///
+/// Passed to the closure of
+/// [`CompMut::for_each_with_others`](crate::entity::CompMut::for_each_with_others).
+#[derive(Clone, Copy)]
+pub struct OtherComponents<'a, T> {
+    index: usize,
+    entities: &'a [Entity],
+    sparse: &'a SparseVec,
+    before: &'a [T],
+    after: &'a [T],
+}
+
+impl<'a, T> OtherComponents<'a, T> {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(
+        index: usize,
+        entities: &'a [Entity],
+        sparse: &'a SparseVec,
+        before: &'a [T],
+        after: &'a [T],
+    ) -> Self {
+        Self {
+            index,
+            entities,
+            sparse,
+            before,
+            after,
+        }
+    }
+
+    /// Returns a reference to the component mapped to `entity`, if it exists and is not the
+    /// component currently being mutated.
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<&'a T> {
+        let dense = self.sparse.get(entity)?.dense();
+
+        if dense < self.index {
+            self.before.get(dense)
+        } else if dense > self.index {
+            self.after.get(dense - self.index - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether `entity` is present in the view.
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.get(entity).is_some()
+    }
+
+    /// Returns the number of components in the view.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.before.len() + self.after.len()
+    }
+
+    /// Returns whether the view is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over all entities and components in the view.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &'a T)> + 'a {
+        let before = self.entities[..self.index].iter().zip(self.before);
+        let after = self.entities[self.index + 1..].iter().zip(self.after);
+        before
+            .chain(after)
+            .map(|(&entity, component)| (entity, component))
+    }
+}
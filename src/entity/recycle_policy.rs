@@ -0,0 +1,12 @@
+/// Controls when the indexes of destroyed entities can be reused.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum RecyclePolicy {
+    /// Indexes can be reused as soon as the entity is destroyed. Keeps entity indexes as low as
+    /// possible.
+    #[default]
+    Immediate,
+    /// Indexes can only be reused after the given number of calls to
+    /// [`maintain`](crate::entity::EntityStorage::maintain). Stale handles to destroyed entities
+    /// cannot alias new entities during that window, at the cost of higher entity indexes.
+    Deferred(u32),
+}
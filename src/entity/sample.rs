@@ -0,0 +1,78 @@
+use crate::entity::Entity;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::iter::FusedIterator;
+
+/// Iterator over randomly chosen components of a view.
+///
+/// Created with [`Comp::sample`](crate::entity::Comp::sample) and
+/// [`Comp::sample_seeded`](crate::entity::Comp::sample_seeded).
+#[derive(Clone, Debug)]
+pub struct Sample<'a, T> {
+    entities: &'a [Entity],
+    components: &'a [T],
+    index: usize,
+    remaining: usize,
+    state: u64,
+}
+
+impl<'a, T> Sample<'a, T> {
+    #[must_use]
+    pub(crate) fn new(entities: &'a [Entity], components: &'a [T], n: usize, seed: u64) -> Self {
+        Self {
+            entities,
+            components,
+            index: 0,
+            remaining: n.min(components.len()),
+            state: seed,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn random_seed() -> u64 {
+        RandomState::new().build_hasher().finish()
+    }
+
+    // SplitMix64.
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl<'a, T> Iterator for Sample<'a, T> {
+    type Item = (Entity, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Selection sampling: each index is chosen with probability remaining / left, so exactly
+        // `remaining` distinct indexes are chosen in dense order.
+        while self.remaining != 0 {
+            let index = self.index;
+            let left = (self.components.len() - index) as u64;
+            self.index += 1;
+
+            if ((u128::from(self.next_u64()) * u128::from(left)) >> 64) < self.remaining as u128 {
+                self.remaining -= 1;
+                return Some((self.entities[index], &self.components[index]));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Sample<'_, T> {
+    // Empty
+}
+
+impl<T> FusedIterator for Sample<'_, T> {
+    // Empty
+}
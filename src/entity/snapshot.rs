@@ -0,0 +1,9 @@
+use crate::entity::EntityStorage;
+
+/// Copy of all entities and components in an [`EntityStorage`].
+///
+/// Created with [`EntityStorage::snapshot`] and applied with [`EntityStorage::restore`].
+#[derive(Debug)]
+pub struct EntitySnapshot {
+    pub(crate) storage: EntityStorage,
+}
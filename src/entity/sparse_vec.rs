@@ -116,12 +116,25 @@ impl SparseVec {
         self.entities.clear();
     }
 
+    /// Removes trailing empty slots and shrinks the capacity of the sparse vec as much as
+    /// possible.
+    pub fn shrink_to_fit(&mut self) {
+        let len = self
+            .entities
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |i| i + 1);
+
+        self.entities.truncate(len);
+        self.entities.shrink_to_fit();
+    }
+
     #[cold]
     fn extend_to_index(&mut self, index: usize) {
         let extra_len =
             index.checked_next_power_of_two().unwrap_or(index) - self.entities.len() + 1;
 
-        self.entities.extend(iter::repeat(None).take(extra_len));
+        self.entities.extend(iter::repeat_n(None, extra_len));
     }
 }
 
@@ -34,14 +34,17 @@ pub mod prelude {
     pub use crate::entity::{Comp, CompMut, Entities, Entity, EntityStorage, GroupLayout};
     pub use crate::query::{BuildCompoundQuery, IntoEntityIter, Query};
     pub use crate::resource::{Res, ResMut, ResourceStorage};
-    pub use crate::system::{IntoSystem, Run, System};
+    pub use crate::system::{In, IntoSystem, Run, RunWithInput, System};
     pub use crate::World;
 }
 
-use crate::entity::{EntityStorage, GroupLayout};
-use crate::resource::ResourceStorage;
+use crate::entity::{EntitySnapshot, EntityStorage, GroupLayout, SnapshotError};
+use crate::resource::{ResourceSnapshot, ResourceStorage};
 
 /// Storage for entities and resources.
+///
+/// The world is `Sync`, so functions borrowing data through [`run`](Self::run) can be run from
+/// multiple threads at once. Conflicting borrows panic instead of blocking.
 #[derive(Default, Debug)]
 pub struct World {
     /// Storage for entities.
@@ -68,6 +71,19 @@ impl World {
         self.entities.is_empty() && self.resources.is_empty()
     }
 
+    /// Reserves capacity for at least `additional` more entities.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.entities.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the entity and resource storages as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.entities.shrink_to_fit();
+        self.resources.shrink_to_fit();
+    }
+
     /// Removes all entities and all resources from the storage.
     #[inline]
     pub fn clear(&mut self) {
@@ -75,6 +91,18 @@ impl World {
         self.resources.clear();
     }
 
+    /// Removes all entities from the storage, keeping the resources.
+    #[inline]
+    pub fn clear_entities(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Removes all resources from the storage, keeping the entities.
+    #[inline]
+    pub fn clear_resources(&mut self) {
+        self.resources.clear();
+    }
+
     /// Removes all entities and all resources from the storage and resets the entity allocator.
     ///
     /// After this call, the storage is allowed to return previously allocated entities.
@@ -83,4 +111,42 @@ impl World {
         self.entities.reset();
         self.resources.clear();
     }
+
+    /// Returns a copy of all entities, components and cloneable resources in the world, to be
+    /// applied later with [`restore`](Self::restore).
+    ///
+    /// See [`EntityStorage::snapshot`] and [`ResourceStorage::snapshot`].
+    pub fn snapshot(&self) -> Result<WorldSnapshot, SnapshotError> {
+        Ok(WorldSnapshot {
+            entities: self.entities.snapshot()?,
+            resources: self.resources.snapshot(),
+        })
+    }
+
+    /// Replaces the entities, components and cloneable resources in the world with the ones in
+    /// the `snapshot`.
+    ///
+    /// See [`EntityStorage::restore`] and [`ResourceStorage::restore`].
+    pub fn restore(&mut self, snapshot: WorldSnapshot) {
+        self.entities.restore(snapshot.entities);
+        self.resources.restore(snapshot.resources);
+    }
+
+    /// Runs `f` and returns all component and resource borrows performed on the current thread
+    /// while it ran.
+    ///
+    /// Useful in tests for checking that a code path only borrows the data it claims to.
+    #[cfg(feature = "trace")]
+    pub fn with_access_trace(&mut self, f: impl FnOnce(&mut World)) -> util::AccessLog {
+        util::collect_accesses(|| f(self))
+    }
+}
+
+/// Copy of the entities, components and cloneable resources in a [`World`].
+///
+/// Created with [`World::snapshot`] and applied with [`World::restore`].
+#[derive(Debug)]
+pub struct WorldSnapshot {
+    entities: EntitySnapshot,
+    resources: ResourceSnapshot,
 }
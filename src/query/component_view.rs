@@ -1,4 +1,5 @@
 use crate::entity::{Comp, CompMut, Component, Entity, GroupInfo, SparseVec};
+use crate::util::TypeData;
 use std::ops::Range;
 
 /// Represents a view over components of a given type.
@@ -33,7 +34,13 @@ pub unsafe trait ComponentView {
 
     /// Returns the group info associated with the view, if any.
     #[must_use]
-    fn group_info(&self) -> Option<GroupInfo>;
+    fn group_info(&self) -> Option<GroupInfo<'_>>;
+
+    /// Returns the type of the components in the view, if known.
+    #[must_use]
+    fn type_data(&self) -> Option<TypeData> {
+        None
+    }
 
     /// Splits the view into its entities, sparse vec and pointer to the components.
     #[must_use]
@@ -97,10 +104,14 @@ macro_rules! impl_comp_common {
                 $Comp::len(self)
             }
 
-            fn group_info(&self) -> Option<GroupInfo> {
+            fn group_info(&self) -> Option<GroupInfo<'_>> {
                 $Comp::group_info(self)
             }
 
+            fn type_data(&self) -> Option<TypeData> {
+                Some(TypeData::new::<T>())
+            }
+
             fn split<'a>(self) -> (&'a [Entity], &'a SparseVec, Self::Ptr)
             where
                 Self: 'a,
@@ -180,10 +191,14 @@ where
         CompMut::len(self)
     }
 
-    fn group_info(&self) -> Option<GroupInfo> {
+    fn group_info(&self) -> Option<GroupInfo<'_>> {
         CompMut::group_info(self)
     }
 
+    fn type_data(&self) -> Option<TypeData> {
+        Some(TypeData::new::<T>())
+    }
+
     fn split<'a>(self) -> (&'a [Entity], &'a SparseVec, Self::Ptr)
     where
         Self: 'a,
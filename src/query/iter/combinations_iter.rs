@@ -0,0 +1,65 @@
+use crate::query::QueryPart;
+use std::iter::FusedIterator;
+
+/// Iterator over all distinct combinations of `K` component sets that match a query.
+pub struct CombinationsIter<'a, G, const K: usize>
+where
+    G: QueryPart + 'a,
+{
+    items: Vec<G::Refs<'a>>,
+    indexes: [usize; K],
+    done: bool,
+}
+
+impl<'a, G, const K: usize> CombinationsIter<'a, G, K>
+where
+    G: QueryPart + 'a,
+{
+    pub(crate) fn new(items: Vec<G::Refs<'a>>) -> Self {
+        Self {
+            done: K > items.len(),
+            items,
+            indexes: std::array::from_fn(|i| i),
+        }
+    }
+}
+
+impl<'a, G, const K: usize> Iterator for CombinationsIter<'a, G, K>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Copy,
+{
+    type Item = [G::Refs<'a>; K];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let combination = self.indexes.map(|i| self.items[i]);
+
+        // Advance the rightmost index that can still be incremented and reset the ones after it.
+        let n = self.items.len();
+
+        match (0..K).rev().find(|&i| self.indexes[i] < n - K + i) {
+            Some(i) => {
+                self.indexes[i] += 1;
+
+                for j in (i + 1)..K {
+                    self.indexes[j] = self.indexes[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(combination)
+    }
+}
+
+impl<'a, G, const K: usize> FusedIterator for CombinationsIter<'a, G, K>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Copy,
+{
+    // Empty
+}
@@ -22,6 +22,31 @@ where
             ptrs,
         }
     }
+
+    #[must_use]
+    pub(crate) fn remaining_entities(&self) -> usize {
+        self.entities.len() - self.index
+    }
+
+    #[cfg(feature = "parallel")]
+    #[must_use]
+    pub(crate) fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.index + index;
+
+        let left = Self {
+            index: self.index,
+            entities: &self.entities[..mid],
+            ptrs: self.ptrs,
+        };
+
+        let right = Self {
+            index: mid,
+            entities: self.entities,
+            ptrs: self.ptrs,
+        };
+
+        (left, right)
+    }
 }
 
 impl<'a, G> Iterator for DenseIter<'a, G>
@@ -42,6 +67,11 @@ where
         unsafe { Some(G::get_dense(self.ptrs, index)) }
     }
 
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining_entities();
+        (len, Some(len))
+    }
+
     fn fold<B, F>(mut self, mut init: B, mut f: F) -> B
     where
         F: FnMut(B, Self::Item) -> B,
@@ -55,6 +85,29 @@ where
     }
 }
 
+impl<'a, G> DoubleEndedIterator for DenseIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index >= self.entities.len() {
+            return None;
+        }
+
+        let index = self.entities.len() - 1;
+        self.entities = &self.entities[..index];
+
+        unsafe { Some(G::get_dense(self.ptrs, index)) }
+    }
+}
+
+impl<'a, G> ExactSizeIterator for DenseIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    // Empty
+}
+
 impl<'a, G> EntityIterator for DenseIter<'a, G>
 where
     G: QueryPart + 'a,
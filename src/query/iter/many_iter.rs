@@ -0,0 +1,90 @@
+use crate::entity::Entity;
+use crate::query::{EntityIterator, QueryPart};
+
+/// Iterator over the components of a given list of entities that match a query.
+pub struct ManyIter<'a, G, I, E, J>
+where
+    G: QueryPart,
+    I: QueryPart,
+    E: QueryPart,
+{
+    entities: J,
+    sparse: G::Sparse<'a>,
+    include: I::Sparse<'a>,
+    exclude: E::Sparse<'a>,
+    ptrs: G::Ptrs,
+}
+
+impl<'a, G, I, E, J> ManyIter<'a, G, I, E, J>
+where
+    G: QueryPart,
+    I: QueryPart,
+    E: QueryPart,
+{
+    pub(crate) unsafe fn new(
+        entities: J,
+        sparse: G::Sparse<'a>,
+        include: I::Sparse<'a>,
+        exclude: E::Sparse<'a>,
+        ptrs: G::Ptrs,
+    ) -> Self {
+        Self {
+            entities,
+            sparse,
+            include,
+            exclude,
+            ptrs,
+        }
+    }
+}
+
+impl<'a, G, I, E, J> Iterator for ManyIter<'a, G, I, E, J>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    J: Iterator<Item = Entity>,
+{
+    type Item = G::Refs<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_entity().map(|(_, components)| components)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.entities.size_hint().1)
+    }
+}
+
+impl<'a, G, I, E, J> EntityIterator for ManyIter<'a, G, I, E, J>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    J: Iterator<Item = Entity>,
+{
+    fn next_with_entity(&mut self) -> Option<(Entity, Self::Item)> {
+        loop {
+            let entity = self.entities.next()?;
+
+            // Unlike entities from the dense arrays, the given entities may be stale.
+            if !G::sparse_contains_all(self.sparse, entity) {
+                continue;
+            }
+
+            if !I::sparse_contains_all(self.include, entity) {
+                continue;
+            }
+
+            if !E::sparse_contains_none(self.exclude, entity) {
+                continue;
+            }
+
+            if let Some(components) =
+                unsafe { G::get_sparse(self.sparse, self.ptrs, entity.sparse()) }
+            {
+                return Some((entity, components));
+            }
+        }
+    }
+}
@@ -1,9 +1,19 @@
+mod combinations_iter;
 mod dense_iter;
 mod entity_iter;
+mod many_iter;
+#[cfg(feature = "parallel")]
+mod par_iter;
+mod sorted_iter;
 mod sparse_iter;
 
+pub use self::combinations_iter::*;
 pub use self::dense_iter::*;
 pub use self::entity_iter::*;
+pub use self::many_iter::*;
+#[cfg(feature = "parallel")]
+pub use self::par_iter::*;
+pub use self::sorted_iter::*;
 pub use self::sparse_iter::*;
 
 use crate::entity::Entity;
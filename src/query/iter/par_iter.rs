@@ -0,0 +1,233 @@
+use crate::entity::{Comp, CompMut, Component};
+use crate::query::{DenseIter, Iter, Query, QueryPart, SparseIter};
+use rayon::iter::plumbing::{
+    bridge, bridge_unindexed, Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer,
+    UnindexedProducer,
+};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// Parallel iterator over all components that match a query.
+///
+/// Splits the entities driving the query into chunks that are iterated on the rayon thread pool.
+pub enum ParIter<'a, G, I, E>
+where
+    G: QueryPart,
+    I: QueryPart,
+    E: QueryPart,
+{
+    /// Parallel iterator over ungrouped queries.
+    Sparse(SparseParIter<'a, G, I, E>),
+    /// Parallel iterator over grouped queries. Supports indexed operations like `zip`.
+    Dense(DenseParIter<'a, G>),
+}
+
+impl<'a, G, I, E> ParIter<'a, G, I, E>
+where
+    G: QueryPart,
+    I: QueryPart,
+    E: QueryPart,
+{
+    pub(crate) fn new(iter: Iter<'a, G, I, E>) -> Self {
+        match iter {
+            Iter::Sparse(iter) => Self::Sparse(SparseParIter { iter }),
+            Iter::Dense(iter) => Self::Dense(DenseParIter { iter }),
+        }
+    }
+
+    /// Returns whether the iterator is sparse.
+    #[must_use]
+    pub const fn is_sparse(&self) -> bool {
+        matches!(self, Self::Sparse(_))
+    }
+
+    /// Returns whether the iterator is dense.
+    #[must_use]
+    pub const fn is_dense(&self) -> bool {
+        matches!(self, Self::Dense(_))
+    }
+}
+
+impl<'a, G, I, E> ParallelIterator for ParIter<'a, G, I, E>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    G::Refs<'a>: Send,
+{
+    type Item = G::Refs<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        match self {
+            Self::Sparse(iter) => iter.drive_unindexed(consumer),
+            Self::Dense(iter) => iter.drive_unindexed(consumer),
+        }
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        match self {
+            Self::Sparse(_) => None,
+            Self::Dense(iter) => Some(iter.len()),
+        }
+    }
+}
+
+/// Parallel iterator over ungrouped queries.
+pub struct SparseParIter<'a, G, I, E>
+where
+    G: QueryPart,
+    I: QueryPart,
+    E: QueryPart,
+{
+    iter: SparseIter<'a, G, I, E>,
+}
+
+// Each split of the iterator yields components of distinct entities, so sending it to another
+// thread is safe as long as the yielded components can be sent.
+unsafe impl<'a, G, I, E> Send for SparseParIter<'a, G, I, E>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    G::Refs<'a>: Send,
+{
+    // Empty
+}
+
+impl<'a, G, I, E> UnindexedProducer for SparseParIter<'a, G, I, E>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    G::Refs<'a>: Send,
+{
+    type Item = G::Refs<'a>;
+
+    fn split(self) -> (Self, Option<Self>) {
+        let (left, right) = self.iter.split();
+        (Self { iter: left }, right.map(|iter| Self { iter }))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        folder.consume_iter(self.iter)
+    }
+}
+
+impl<'a, G, I, E> ParallelIterator for SparseParIter<'a, G, I, E>
+where
+    G: QueryPart + 'a,
+    I: QueryPart,
+    E: QueryPart,
+    G::Refs<'a>: Send,
+{
+    type Item = G::Refs<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self, consumer)
+    }
+}
+
+/// Parallel iterator over grouped queries.
+pub struct DenseParIter<'a, G>
+where
+    G: QueryPart,
+{
+    iter: DenseIter<'a, G>,
+}
+
+// Same as for `SparseParIter`.
+unsafe impl<'a, G> Send for DenseParIter<'a, G>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Send,
+{
+    // Empty
+}
+
+impl<'a, G> Producer for DenseParIter<'a, G>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Send,
+{
+    type Item = G::Refs<'a>;
+    type IntoIter = DenseIter<'a, G>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.iter.split_at(index);
+        (Self { iter: left }, Self { iter: right })
+    }
+}
+
+impl<'a, G> ParallelIterator for DenseParIter<'a, G>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Send,
+{
+    type Item = G::Refs<'a>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+impl<'a, G> IndexedParallelIterator for DenseParIter<'a, G>
+where
+    G: QueryPart + 'a,
+    G::Refs<'a>: Send,
+{
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: Consumer<Self::Item>,
+    {
+        bridge(self, consumer)
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: ProducerCallback<Self::Item>,
+    {
+        callback.callback(self)
+    }
+}
+
+macro_rules! impl_into_par_iter {
+    ($Comp:ident) => {
+        impl<'a, T> IntoParallelIterator for &'a $Comp<'_, T>
+        where
+            T: Component,
+        {
+            type Iter = ParIter<'a, Self, (), ()>;
+            type Item = &'a T;
+
+            fn into_par_iter(self) -> Self::Iter {
+                Query::par_iter(self)
+            }
+        }
+    };
+}
+
+impl_into_par_iter!(Comp);
+impl_into_par_iter!(CompMut);
@@ -0,0 +1,53 @@
+use crate::entity::Entity;
+use crate::query::{EntityIterator, QueryPart};
+use std::vec::IntoIter as VecIntoIter;
+
+/// Iterator over components collected from a query and sorted.
+pub struct SortedIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    items: VecIntoIter<(Entity, G::Refs<'a>)>,
+}
+
+impl<'a, G> SortedIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    pub(crate) fn new(items: Vec<(Entity, G::Refs<'a>)>) -> Self {
+        Self {
+            items: items.into_iter(),
+        }
+    }
+}
+
+impl<'a, G> Iterator for SortedIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    type Item = G::Refs<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(_, components)| components)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+
+impl<'a, G> ExactSizeIterator for SortedIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    // Empty
+}
+
+impl<'a, G> EntityIterator for SortedIter<'a, G>
+where
+    G: QueryPart + 'a,
+{
+    fn next_with_entity(&mut self) -> Option<(Entity, Self::Item)> {
+        self.items.next()
+    }
+}
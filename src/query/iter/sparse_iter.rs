@@ -37,6 +37,46 @@ where
             ptrs,
         }
     }
+
+    #[must_use]
+    pub(crate) fn remaining_entities(&self) -> usize {
+        self.entities.len()
+    }
+
+    #[cfg(feature = "parallel")]
+    #[must_use]
+    pub(crate) fn split(self) -> (Self, Option<Self>) {
+        let entities = self.entities.as_slice();
+
+        if entities.len() < 2 {
+            return (self, None);
+        }
+
+        let (left, right) = entities.split_at(entities.len() / 2);
+
+        let left = Self {
+            entities: left.iter(),
+            ..self
+        };
+
+        let right = Self {
+            entities: right.iter(),
+            ..self
+        };
+
+        (left, Some(right))
+    }
+
+    #[must_use]
+    pub(crate) fn count_matches(self) -> usize {
+        self.entities
+            .filter(|&&entity| {
+                G::sparse_contains_all(self.sparse, entity)
+                    && I::sparse_contains_all(self.include, entity)
+                    && E::sparse_contains_none(self.exclude, entity)
+            })
+            .count()
+    }
 }
 
 impl<'a, G, I, E> Iterator for SparseIter<'a, G, I, E>
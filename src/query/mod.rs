@@ -6,6 +6,7 @@ mod into_query_parts;
 mod iter;
 mod query_group_info;
 mod query_part;
+mod query_plan;
 
 pub use self::component_view::*;
 pub use self::compound_query::*;
@@ -13,8 +14,12 @@ pub use self::into_query_parts::*;
 pub use self::iter::*;
 pub use self::query_group_info::*;
 pub use self::query_part::*;
+pub use self::query_plan::*;
 
 use crate::entity::Entity;
+use std::cmp::Ordering;
+use std::ops::ControlFlow;
+use std::vec;
 
 /// Trait for all queries that can be performed on component views.
 pub trait Query: IntoQueryParts {
@@ -31,6 +36,99 @@ pub trait Query: IntoQueryParts {
     where
         Self: 'a;
 
+    /// Returns a parallel iterator over all components that match the query.
+    ///
+    /// Grouped queries return [`ParIter::Dense`], which supports indexed operations like `zip`
+    /// and `collect_into_vec`. Single component views also implement `IntoParallelIterator`; use
+    /// `into_par_iter` on them when the rayon prelude is in scope.
+    #[cfg(feature = "parallel")]
+    fn par_iter<'a>(self) -> ParIter<'a, Self::Get, Self::Include, Self::Exclude>
+    where
+        Self: 'a,
+        <Self::Get as QueryPart>::Refs<'a>: Send;
+
+    /// Returns an iterator over the components of the given `entities`, in the order they are
+    /// given. Entities that don't match the query are skipped.
+    fn iter_many<'a, J>(
+        self,
+        entities: J,
+    ) -> ManyIter<'a, Self::Get, Self::Include, Self::Exclude, J::IntoIter>
+    where
+        Self: 'a,
+        J: IntoIterator<Item = Entity>,
+        <Self::Get as QueryPart>::Refs<'a>: Copy;
+
+    /// Returns an iterator over the components of the given `entities`, in the order they are
+    /// given. Entities that don't match the query are skipped.
+    ///
+    /// Unlike [`iter_many`](Self::iter_many), works with mutable queries. Panics if `entities`
+    /// contains duplicates.
+    fn iter_many_mut<'a, J>(
+        self,
+        entities: J,
+    ) -> ManyIter<'a, Self::Get, Self::Include, Self::Exclude, vec::IntoIter<Entity>>
+    where
+        Self: 'a,
+        J: IntoIterator<Item = Entity>;
+
+    /// Returns the number of entities that match the query.
+    ///
+    /// Grouped queries are counted in constant time. Other queries test the membership of each
+    /// entity in the smallest storage, without accessing the components.
+    #[must_use]
+    fn count<'a>(self) -> usize
+    where
+        Self: 'a;
+
+    /// Returns how the query would be iterated with the current `GroupLayout`, without running
+    /// it.
+    #[must_use]
+    fn plan<'a>(self) -> QueryPlan
+    where
+        Self: 'a;
+
+    /// Returns an iterator over all components that match the query, sorted by entity index.
+    ///
+    /// All matching components are collected into a vector and sorted before iteration, so the
+    /// call allocates and runs in `O(n log n)` time.
+    fn sorted<'a>(self) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a;
+
+    /// Returns an iterator over all components that match the query, sorted with the comparator
+    /// function `compare`. The sort is stable.
+    ///
+    /// All matching components are collected into a vector and sorted before iteration, so the
+    /// call allocates and runs in `O(n log n)` time.
+    fn sorted_by<'a, F>(self, compare: F) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a,
+        F: FnMut(
+            &<Self::Get as QueryPart>::Refs<'a>,
+            &<Self::Get as QueryPart>::Refs<'a>,
+        ) -> Ordering;
+
+    /// Returns an iterator over all components that match the query, sorted by the key returned
+    /// by `f`. The sort is stable.
+    ///
+    /// All matching components are collected into a vector and sorted before iteration, so the
+    /// call allocates and runs in `O(n log n)` time.
+    fn sorted_by_key<'a, K, F>(self, f: F) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a,
+        K: Ord,
+        F: FnMut(&<Self::Get as QueryPart>::Refs<'a>) -> K;
+
+    /// Returns an iterator over all distinct combinations of `K` component sets that match the
+    /// query. Each combination is yielded once, with its entities in matching order.
+    ///
+    /// All matching components are collected into a vector before iteration. Iterating all
+    /// combinations takes `O(n^K)` time.
+    fn iter_combinations<'a, const K: usize>(self) -> CombinationsIter<'a, Self::Get, K>
+    where
+        Self: 'a,
+        <Self::Get as QueryPart>::Refs<'a>: Copy;
+
     /// Runs a function for each component set that matches the query.
     fn for_each<'a, F>(self, f: F)
     where
@@ -43,6 +141,24 @@ pub trait Query: IntoQueryParts {
         Self: 'a,
         F: FnMut((Entity, <Self::Get as QueryPart>::Refs<'a>));
 
+    /// Runs a function for each component set that matches the query, stopping as soon as the
+    /// function returns [`ControlFlow::Break`].
+    ///
+    /// Returns the value the iteration was stopped with, if any.
+    fn for_each_while<'a, B, F>(self, f: F) -> ControlFlow<B>
+    where
+        Self: 'a,
+        F: FnMut(<Self::Get as QueryPart>::Refs<'a>) -> ControlFlow<B>;
+
+    /// Runs a function for each entity and component set that matches the query, stopping as
+    /// soon as the function returns [`ControlFlow::Break`].
+    ///
+    /// Returns the value the iteration was stopped with, if any.
+    fn for_each_while_with_entity<'a, B, F>(self, f: F) -> ControlFlow<B>
+    where
+        Self: 'a,
+        F: FnMut((Entity, <Self::Get as QueryPart>::Refs<'a>)) -> ControlFlow<B>;
+
     /// Returns the entities that match the query, if the query is grouped.
     #[must_use]
     fn group_entities<'a>(self) -> Option<&'a [Entity]>
@@ -87,6 +203,166 @@ where
         Iter::new(get, include, exclude)
     }
 
+    #[cfg(feature = "parallel")]
+    fn par_iter<'a>(self) -> ParIter<'a, Self::Get, Self::Include, Self::Exclude>
+    where
+        Self: 'a,
+        <Self::Get as QueryPart>::Refs<'a>: Send,
+    {
+        ParIter::new(self.iter())
+    }
+
+    fn iter_many<'a, J>(
+        self,
+        entities: J,
+    ) -> ManyIter<'a, Self::Get, Self::Include, Self::Exclude, J::IntoIter>
+    where
+        Self: 'a,
+        J: IntoIterator<Item = Entity>,
+        <Self::Get as QueryPart>::Refs<'a>: Copy,
+    {
+        let (get, include, exclude) = self.into_query_parts();
+        let (_, sparse, ptrs) = get.split_sparse();
+        let (_, include) = include.split_filter();
+        let (_, exclude) = exclude.split_filter();
+
+        unsafe { ManyIter::new(entities.into_iter(), sparse, include, exclude, ptrs) }
+    }
+
+    fn iter_many_mut<'a, J>(
+        self,
+        entities: J,
+    ) -> ManyIter<'a, Self::Get, Self::Include, Self::Exclude, vec::IntoIter<Entity>>
+    where
+        Self: 'a,
+        J: IntoIterator<Item = Entity>,
+    {
+        let entities = entities.into_iter().collect::<Vec<_>>();
+
+        let mut sorted_entities = entities.clone();
+        sorted_entities.sort_unstable();
+
+        if let Some(entity) = sorted_entities
+            .windows(2)
+            .find_map(|pair| (pair[0] == pair[1]).then_some(pair[0]))
+        {
+            panic_duplicate_entity(entity);
+        }
+
+        let (get, include, exclude) = self.into_query_parts();
+        let (_, sparse, ptrs) = get.split_sparse();
+        let (_, include) = include.split_filter();
+        let (_, exclude) = exclude.split_filter();
+
+        unsafe { ManyIter::new(entities.into_iter(), sparse, include, exclude, ptrs) }
+    }
+
+    fn count<'a>(self) -> usize
+    where
+        Self: 'a,
+    {
+        let (get, include, exclude) = self.into_query_parts();
+
+        if let Some(range) = group_range(&get, &include, &exclude) {
+            return range.len();
+        }
+
+        let (get_entities, sparse, ptrs) = get.split_sparse();
+        let (include_entities, include) = include.split_filter();
+        let (_, exclude) = exclude.split_filter();
+
+        let entities = match (Self::Get::HAS_DATA, Self::Include::HAS_DATA) {
+            (true, false) => get_entities,
+            (false, true) => include_entities,
+            (true, true) => {
+                if get_entities.len() <= include_entities.len() {
+                    get_entities
+                } else {
+                    include_entities
+                }
+            }
+            (false, false) => panic!("Cannot iterate over an empty Query"),
+        };
+
+        let iter: SparseIter<Self::Get, Self::Include, Self::Exclude> =
+            unsafe { SparseIter::new(entities, sparse, include, exclude, ptrs) };
+
+        iter.count_matches()
+    }
+
+    fn plan<'a>(self) -> QueryPlan
+    where
+        Self: 'a,
+    {
+        let (get, include, exclude) = self.into_query_parts();
+
+        // Same choice of entities as the sparse path of `Iter::new`.
+        let driving_view = match (get.shortest_view(), include.shortest_view()) {
+            (Some(get), Some(include)) => {
+                if get.1 >= include.1 {
+                    Some(get)
+                } else {
+                    Some(include)
+                }
+            }
+            (view, None) | (None, view) => view,
+        };
+
+        match Iter::new(get, include, exclude) {
+            Iter::Sparse(iter) => {
+                let len = iter.remaining_entities();
+
+                let driving_component = driving_view
+                    .filter(|&(_, view_len)| view_len == len)
+                    .map(|(type_data, _)| type_data);
+
+                QueryPlan::new(false, driving_component, len)
+            }
+            Iter::Dense(iter) => QueryPlan::new(true, None, iter.remaining_entities()),
+        }
+    }
+
+    fn sorted<'a>(self) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a,
+    {
+        let mut items = self.iter().with_entity().collect::<Vec<_>>();
+        items.sort_unstable_by_key(|(entity, _)| entity.index);
+        SortedIter::new(items)
+    }
+
+    fn sorted_by<'a, F>(self, mut compare: F) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a,
+        F: FnMut(
+            &<Self::Get as QueryPart>::Refs<'a>,
+            &<Self::Get as QueryPart>::Refs<'a>,
+        ) -> Ordering,
+    {
+        let mut items = self.iter().with_entity().collect::<Vec<_>>();
+        items.sort_by(|(_, a), (_, b)| compare(a, b));
+        SortedIter::new(items)
+    }
+
+    fn sorted_by_key<'a, K, F>(self, mut f: F) -> SortedIter<'a, Self::Get>
+    where
+        Self: 'a,
+        K: Ord,
+        F: FnMut(&<Self::Get as QueryPart>::Refs<'a>) -> K,
+    {
+        let mut items = self.iter().with_entity().collect::<Vec<_>>();
+        items.sort_by_key(|(_, components)| f(components));
+        SortedIter::new(items)
+    }
+
+    fn iter_combinations<'a, const K: usize>(self) -> CombinationsIter<'a, Self::Get, K>
+    where
+        Self: 'a,
+        <Self::Get as QueryPart>::Refs<'a>: Copy,
+    {
+        CombinationsIter::new(self.iter().collect())
+    }
+
     fn for_each<'a, F>(self, f: F)
     where
         Self: 'a,
@@ -103,6 +379,22 @@ where
         self.iter().with_entity().for_each(f);
     }
 
+    fn for_each_while<'a, B, F>(self, f: F) -> ControlFlow<B>
+    where
+        Self: 'a,
+        F: FnMut(<Self::Get as QueryPart>::Refs<'a>) -> ControlFlow<B>,
+    {
+        self.iter().try_for_each(f)
+    }
+
+    fn for_each_while_with_entity<'a, B, F>(self, f: F) -> ControlFlow<B>
+    where
+        Self: 'a,
+        F: FnMut((Entity, <Self::Get as QueryPart>::Refs<'a>)) -> ControlFlow<B>,
+    {
+        self.iter().with_entity().try_for_each(f)
+    }
+
     fn group_entities<'a>(self) -> Option<&'a [Entity]>
     where
         Self: 'a,
@@ -147,3 +439,9 @@ where
         Some((entities, components))
     }
 }
+
+#[cold]
+#[inline(never)]
+fn panic_duplicate_entity(entity: Entity) -> ! {
+    panic!("Entity {entity:?} was passed to 'iter_many_mut' more than once");
+}
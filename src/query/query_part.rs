@@ -1,5 +1,6 @@
 use crate::entity::{Entity, SparseVec};
 use crate::query::{ComponentView, QueryGroupInfo};
+use crate::util::TypeData;
 use std::ops::Range;
 
 /// Helper trait for describing query parts.
@@ -37,7 +38,14 @@ pub unsafe trait QueryPart {
 
     /// Returns the combined group info of all component views.
     #[must_use]
-    fn group_info(&self) -> Option<QueryGroupInfo>;
+    fn group_info(&self) -> Option<QueryGroupInfo<'_>>;
+
+    /// Returns the type and length of the component view with the fewest components, if any
+    /// and if its type is known.
+    #[must_use]
+    fn shortest_view(&self) -> Option<(TypeData, usize)> {
+        None
+    }
 
     /// Splits the query part for sparse iteration.
     #[must_use]
@@ -129,7 +137,7 @@ unsafe impl QueryPart for () {
     }
 
     #[inline(always)]
-    fn group_info(&self) -> Option<QueryGroupInfo> {
+    fn group_info(&self) -> Option<QueryGroupInfo<'_>> {
         Some(QueryGroupInfo::Empty)
     }
 
@@ -222,13 +230,17 @@ where
         !ComponentView::contains(self, entity)
     }
 
-    fn group_info(&self) -> Option<QueryGroupInfo> {
+    fn group_info(&self) -> Option<QueryGroupInfo<'_>> {
         Some(QueryGroupInfo::Single {
             len: ComponentView::len(self),
             group_info: ComponentView::group_info(self),
         })
     }
 
+    fn shortest_view(&self) -> Option<(TypeData, usize)> {
+        Some((ComponentView::type_data(self)?, ComponentView::len(self)))
+    }
+
     fn split_sparse<'a>(self) -> (&'a [Entity], Self::Sparse<'a>, Self::Ptrs)
     where
         Self: 'a,
@@ -335,10 +347,19 @@ macro_rules! impl_query_part {
                 )&&+
             }
 
-            fn group_info(&self) -> Option<QueryGroupInfo> {
+            fn group_info(&self) -> Option<QueryGroupInfo<'_>> {
                 group_info!($(&self.$idx),*)
             }
 
+            fn shortest_view(&self) -> Option<(TypeData, usize)> {
+                [$(
+                    (self.$idx.type_data(), self.$idx.len()),
+                )+]
+                .into_iter()
+                .reduce(|shortest, view| if view.1 < shortest.1 { view } else { shortest })
+                .and_then(|(type_data, len)| Some((type_data?, len)))
+            }
+
             fn split_sparse<'a>(self) -> (&'a [Entity], Self::Sparse<'a>, Self::Ptrs)
             where
                 Self: 'a,
@@ -0,0 +1,60 @@
+use crate::util::TypeData;
+
+/// Describes how a query is iterated, without running it.
+///
+/// Returned by [`Query::plan`](crate::query::Query::plan).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct QueryPlan {
+    dense: bool,
+    driving_component: Option<TypeData>,
+    entities_visited: usize,
+}
+
+impl QueryPlan {
+    #[inline]
+    #[must_use]
+    pub(crate) const fn new(
+        dense: bool,
+        driving_component: Option<TypeData>,
+        entities_visited: usize,
+    ) -> Self {
+        Self {
+            dense,
+            driving_component,
+            entities_visited,
+        }
+    }
+
+    /// Returns whether the query iterates packed component arrays. This is only possible when
+    /// all components in the query are grouped together by the current `GroupLayout`.
+    #[inline]
+    #[must_use]
+    pub const fn is_dense(&self) -> bool {
+        self.dense
+    }
+
+    /// Returns whether the query looks up the components of each entity individually.
+    #[inline]
+    #[must_use]
+    pub const fn is_sparse(&self) -> bool {
+        !self.dense
+    }
+
+    /// Returns the type of the component whose entities drive sparse iteration, or `None` if the
+    /// query is dense or the driving component view doesn't report its type.
+    #[inline]
+    #[must_use]
+    pub const fn driving_component(&self) -> Option<TypeData> {
+        self.driving_component
+    }
+
+    /// Returns the number of entities the query visits.
+    ///
+    /// Dense queries only visit matching entities. Sparse queries visit all entities in the
+    /// storage driving the iteration and skip those that don't match.
+    #[inline]
+    #[must_use]
+    pub const fn entities_visited(&self) -> usize {
+        self.entities_visited
+    }
+}
@@ -1,12 +1,61 @@
+use crate::util::AccessTrace;
 use atomic_refcell::{AtomicRef, AtomicRefMut};
 use std::fmt;
 use std::ops::{Deref, DerefMut};
 
 /// Shared borrow over a resource of type `T`.
-pub struct Res<'a, T>(pub(crate) AtomicRef<'a, T>);
+pub struct Res<'a, T>(
+    pub(crate) AtomicRef<'a, T>,
+    #[allow(dead_code)] pub(crate) AccessTrace,
+);
 
 /// Exclusive borrow over a resource of type `T`.
-pub struct ResMut<'a, T>(pub(crate) AtomicRefMut<'a, T>);
+pub struct ResMut<'a, T>(
+    pub(crate) AtomicRefMut<'a, T>,
+    #[allow(dead_code)] pub(crate) AccessTrace,
+);
+
+impl<'a, T> Res<'a, T> {
+    /// Projects the borrow into a part of the resource, keeping the original borrow alive.
+    ///
+    /// This is an associated function that needs to be used as `Res::map(...)`.
+    #[inline]
+    #[must_use]
+    pub fn map<U>(orig: Self, f: impl FnOnce(&T) -> &U) -> Res<'a, U> {
+        let Res(resource, trace) = orig;
+        Res(AtomicRef::map(resource, f), trace)
+    }
+}
+
+impl<'a, T> ResMut<'a, T> {
+    /// Projects the borrow into a part of the resource, keeping the original borrow alive.
+    ///
+    /// This is an associated function that needs to be used as `ResMut::map(...)`.
+    #[inline]
+    #[must_use]
+    pub fn map<U>(orig: Self, f: impl FnOnce(&mut T) -> &mut U) -> ResMut<'a, U> {
+        let ResMut(resource, trace) = orig;
+        ResMut(AtomicRefMut::map(resource, f), trace)
+    }
+}
+
+impl<T> ResMut<'_, T>
+where
+    T: PartialEq,
+{
+    /// Overwrites the resource with `value` only if the two are not equal.
+    ///
+    /// Returns whether the resource was overwritten.
+    #[inline]
+    pub fn set_if_neq(&mut self, value: T) -> bool {
+        if *self.0 == value {
+            return false;
+        }
+
+        *self.0 = value;
+        true
+    }
+}
 
 impl<T> DerefMut for ResMut<'_, T> {
     #[inline]
@@ -20,7 +69,6 @@ macro_rules! impl_res_common {
         impl<T> Deref for $Res<'_, T> {
             type Target = T;
 
-            #[must_use]
             fn deref(&self) -> &T {
                 &self.0
             }
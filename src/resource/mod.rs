@@ -2,20 +2,32 @@
 
 mod borrow;
 mod resource;
+mod resource_set;
+mod shared_resource;
+mod snapshot;
 
 pub use self::borrow::*;
 pub use self::resource::*;
+pub use self::resource_set::*;
+pub use self::shared_resource::*;
+pub use self::snapshot::*;
 
+use crate::system::SystemParamKind;
+use crate::util::{AccessTrace, TypeData};
 use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
 use rustc_hash::FxHashMap;
 use std::any::TypeId;
 use std::collections::hash_map::Entry;
 use std::{any, fmt, mem};
 
+type CloneResourceFn = fn(&dyn Resource) -> Box<dyn Resource>;
+
 /// Storage for resources.
 #[derive(Default)]
 pub struct ResourceStorage {
     resources: FxHashMap<TypeId, AtomicRefCell<Box<dyn Resource>>>,
+    constructors: FxHashMap<String, fn(&mut ResourceStorage)>,
+    cloners: FxHashMap<TypeId, CloneResourceFn>,
 }
 
 impl ResourceStorage {
@@ -58,6 +70,116 @@ impl ResourceStorage {
             .map(|cell| unsafe { *cell.into_inner().downcast().unwrap_unchecked() })
     }
 
+    /// Replaces the resource of type `T` with the value returned by `f`, which takes ownership
+    /// of the current value.
+    ///
+    /// Returns whether the resource was present. If `f` panics, the resource is removed.
+    pub fn replace_with<T>(&mut self, f: impl FnOnce(T) -> T) -> bool
+    where
+        T: Resource,
+    {
+        let Some(resource) = self.remove::<T>() else {
+            return false;
+        };
+
+        self.insert(f(resource));
+        true
+    }
+
+    /// Registers `name` as a way to insert the default value of a resource of type `T` with
+    /// [`insert_by_name`](Self::insert_by_name).
+    ///
+    /// Returns whether the name was newly registered. Names that are already registered are
+    /// not overwritten.
+    pub fn register_named<T>(&mut self, name: impl Into<String>) -> bool
+    where
+        T: Resource + Default,
+    {
+        match self.constructors.entry(name.into()) {
+            Entry::Vacant(entry) => {
+                entry.insert(|resources| {
+                    resources.insert(T::default());
+                });
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+
+    /// Inserts the default value of the resource registered under `name`, replacing the
+    /// previous resource of that type, if any.
+    ///
+    /// Returns whether `name` was registered.
+    pub fn insert_by_name(&mut self, name: &str) -> bool {
+        let Some(&constructor) = self.constructors.get(name) else {
+            return false;
+        };
+
+        constructor(self);
+        true
+    }
+
+    /// Registers resources of type `T` to be included in a [`snapshot`](Self::snapshot).
+    ///
+    /// Returns whether the type was newly registered.
+    pub fn register_cloneable<T>(&mut self) -> bool
+    where
+        T: Resource + Clone,
+    {
+        self.cloners
+            .insert(TypeId::of::<T>(), |resource| unsafe {
+                Box::new(resource.downcast_ref::<T>().unwrap_unchecked().clone())
+            })
+            .is_none()
+    }
+
+    /// Returns a copy of all resources whose types were registered with
+    /// [`register_cloneable`](Self::register_cloneable), to be applied later with
+    /// [`restore`](Self::restore).
+    ///
+    /// Panics if any of these resources is already borrowed mutably.
+    #[must_use]
+    pub fn snapshot(&self) -> ResourceSnapshot {
+        let resources = self
+            .cloners
+            .iter()
+            .filter_map(|(type_id, clone)| {
+                let resource = self.resources.get(type_id)?;
+                Some((*type_id, clone(&**resource.borrow())))
+            })
+            .collect();
+
+        ResourceSnapshot { resources }
+    }
+
+    /// Replaces all resources whose types were registered with
+    /// [`register_cloneable`](Self::register_cloneable) with the ones in the `snapshot`. Other
+    /// resources are left untouched.
+    pub fn restore(&mut self, snapshot: ResourceSnapshot) {
+        for type_id in self.cloners.keys() {
+            self.resources.remove(type_id);
+        }
+
+        self.resources.extend(
+            snapshot
+                .resources
+                .into_iter()
+                .map(|(type_id, resource)| (type_id, AtomicRefCell::new(resource))),
+        );
+    }
+
+    /// Inserts a handle to a resource shared with other storages.
+    ///
+    /// The handle is stored as a resource of type `SharedResource<T>`.
+    ///
+    /// Returns the previous handle, if any.
+    pub fn insert_shared<T>(&mut self, resource: SharedResource<T>) -> Option<SharedResource<T>>
+    where
+        T: Resource,
+    {
+        self.insert(resource)
+    }
+
     /// Returns whether the storage contains a resource of type `T`.
     #[must_use]
     pub fn contains<T>(&self) -> bool
@@ -77,9 +199,26 @@ impl ResourceStorage {
             .unwrap_or_else(|| panic_missing_res::<T>())
     }
 
+    /// Returns mutable references to all resources in the set `R`.
+    ///
+    /// Panics if any of the resources is missing or if `R` contains the same resource type more
+    /// than once.
+    #[must_use]
+    pub fn get_many_mut<R>(&mut self) -> R::Mut<'_>
+    where
+        R: ResourceSet,
+    {
+        if let Some(type_data) = R::first_missing(self) {
+            panic_missing_res_in_set(type_data);
+        }
+
+        self.try_get_many_mut::<R>()
+            .unwrap_or_else(|| unreachable!("All resources in the set are present"))
+    }
+
     /// Borrows a resource of type `T` from the storage.
     #[must_use]
-    pub fn borrow<T>(&self) -> Res<T>
+    pub fn borrow<T>(&self) -> Res<'_, T>
     where
         T: Resource,
     {
@@ -89,7 +228,7 @@ impl ResourceStorage {
 
     /// Mutably borrows a resource of type `T` from the storage.
     #[must_use]
-    pub fn borrow_mut<T>(&self) -> ResMut<T>
+    pub fn borrow_mut<T>(&self) -> ResMut<'_, T>
     where
         T: Resource,
     {
@@ -108,29 +247,46 @@ impl ResourceStorage {
             .map(|cell| unsafe { cell.get_mut().downcast_mut().unwrap_unchecked() })
     }
 
+    /// Returns mutable references to all resources in the set `R`, if they all exist.
+    ///
+    /// Panics if `R` contains the same resource type more than once.
+    #[must_use]
+    pub fn try_get_many_mut<R>(&mut self) -> Option<R::Mut<'_>>
+    where
+        R: ResourceSet,
+    {
+        R::try_get_many_mut(self)
+    }
+
     /// Borrows a resource of type `T` from the storage, if it exists.
     #[must_use]
-    pub fn try_borrow<T>(&self) -> Option<Res<T>>
+    pub fn try_borrow<T>(&self) -> Option<Res<'_, T>>
     where
         T: Resource,
     {
         self.resources.get(&TypeId::of::<T>()).map(|cell| {
-            Res(AtomicRef::map(cell.borrow(), |cell| unsafe {
+            let resource = AtomicRef::map(cell.borrow(), |cell| unsafe {
                 cell.downcast_ref().unwrap_unchecked()
-            }))
+            });
+
+            let trace = AccessTrace::new(SystemParamKind::Res(TypeData::new::<T>()));
+            Res(resource, trace)
         })
     }
 
     /// Mutably borrow a resource of type `T` from the storage, if it exists.
     #[must_use]
-    pub fn try_borrow_mut<T>(&self) -> Option<ResMut<T>>
+    pub fn try_borrow_mut<T>(&self) -> Option<ResMut<'_, T>>
     where
         T: Resource,
     {
         self.resources.get(&TypeId::of::<T>()).map(|cell| {
-            ResMut(AtomicRefMut::map(cell.borrow_mut(), |cell| unsafe {
+            let resource = AtomicRefMut::map(cell.borrow_mut(), |cell| unsafe {
                 cell.downcast_mut().unwrap_unchecked()
-            }))
+            });
+
+            let trace = AccessTrace::new(SystemParamKind::ResMut(TypeData::new::<T>()));
+            ResMut(resource, trace)
         })
     }
 
@@ -148,11 +304,17 @@ impl ResourceStorage {
         self.resources.len()
     }
 
-    /// Removes all resources from the storage.
+    /// Removes all resources from the storage. Registered names and cloneable types are kept.
     #[inline]
     pub fn clear(&mut self) {
         self.resources.clear();
     }
+
+    /// Shrinks the capacity of the storage as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.resources.shrink_to_fit();
+    }
 }
 
 impl fmt::Debug for ResourceStorage {
@@ -174,3 +336,12 @@ where
         any::type_name::<T>(),
     );
 }
+
+#[cold]
+#[inline(never)]
+fn panic_missing_res_in_set(type_data: TypeData) -> ! {
+    panic!(
+        "Tried to access missing resource of type '{}'",
+        type_data.type_name(),
+    );
+}
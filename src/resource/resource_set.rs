@@ -0,0 +1,80 @@
+use crate::resource::{Resource, ResourceStorage};
+use crate::util::TypeData;
+use std::any::TypeId;
+
+/// Helper trait for mutably borrowing multiple resources at once.
+pub unsafe trait ResourceSet {
+    /// Mutable references to the resources in the set.
+    type Mut<'a>;
+
+    /// Returns mutable references to all resources in the set, if they are all present.
+    ///
+    /// Panics if the set contains the same resource type more than once.
+    #[must_use]
+    fn try_get_many_mut(resources: &mut ResourceStorage) -> Option<Self::Mut<'_>>;
+
+    /// Returns the type of the first resource in the set that is missing from `resources`, if
+    /// any.
+    #[must_use]
+    fn first_missing(resources: &ResourceStorage) -> Option<TypeData>;
+}
+
+macro_rules! impl_resource_set {
+    ($(($Res:ident, $idx:tt)),+) => {
+        unsafe impl<$($Res,)+> ResourceSet for ($($Res,)+)
+        where
+            $($Res: Resource,)+
+        {
+            type Mut<'a> = ($(&'a mut $Res,)+);
+
+            fn try_get_many_mut(resources: &mut ResourceStorage) -> Option<Self::Mut<'_>> {
+                let type_ids = [$(TypeId::of::<$Res>(),)+];
+
+                for (i, type_id) in type_ids.iter().enumerate() {
+                    assert!(
+                        !type_ids[..i].contains(type_id),
+                        "Tried to mutably borrow the same resource type more than once",
+                    );
+                }
+
+                let cells = ($(
+                    resources.resources.get(&type_ids[$idx])?.as_ptr(),
+                )+);
+
+                unsafe {
+                    Some(($(
+                        (*cells.$idx).downcast_mut::<$Res>().unwrap_unchecked(),
+                    )+))
+                }
+            }
+
+            fn first_missing(resources: &ResourceStorage) -> Option<TypeData> {
+                [$(TypeData::new::<$Res>(),)+]
+                    .into_iter()
+                    .find(|type_data| !resources.resources.contains_key(&type_data.type_id()))
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+mod impls {
+    use super::*;
+
+    impl_resource_set!((A, 0));
+    impl_resource_set!((A, 0), (B, 1));
+    impl_resource_set!((A, 0), (B, 1), (C, 2));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10), (L, 11));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10), (L, 11), (M, 12));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10), (L, 11), (M, 12), (N, 13));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10), (L, 11), (M, 12), (N, 13), (O, 14));
+    impl_resource_set!((A, 0), (B, 1), (C, 2), (D, 3), (E, 4), (F, 5), (G, 6), (H, 7), (I, 8), (J, 9), (K, 10), (L, 11), (M, 12), (N, 13), (O, 14), (P, 15));
+}
@@ -0,0 +1,76 @@
+use crate::resource::{Res, ResMut, Resource};
+use crate::system::SystemParamKind;
+use crate::util::{AccessTrace, TypeData};
+use atomic_refcell::AtomicRefCell;
+use std::fmt;
+use std::sync::Arc;
+
+/// Resource of type `T` shared between multiple [`ResourceStorage`]s.
+///
+/// Cloning a `SharedResource` returns a new handle to the same value, so writes made through one
+/// storage are visible from all others. Insert a handle in each storage with
+/// [`insert_shared`](crate::resource::ResourceStorage::insert_shared).
+///
+/// Systems access the value through the handle, so `Res<SharedResource<T>>` only allows reading
+/// it and `ResMut<SharedResource<T>>` is required for writing. The value may be borrowed from
+/// multiple threads at once, so borrows follow the same rules as [`ResourceStorage::borrow`]: a
+/// conflicting borrow panics. Systems running in different worlds don't know about each other,
+/// so their access to shared resources is not scheduled.
+///
+/// [`ResourceStorage`]: crate::resource::ResourceStorage
+/// [`ResourceStorage::borrow`]: crate::resource::ResourceStorage::borrow
+pub struct SharedResource<T>(Arc<AtomicRefCell<T>>);
+
+impl<T> SharedResource<T>
+where
+    T: Resource,
+{
+    /// Creates a new shared resource.
+    #[inline]
+    #[must_use]
+    pub fn new(resource: T) -> Self {
+        Self(Arc::new(AtomicRefCell::new(resource)))
+    }
+
+    /// Borrows the shared value.
+    #[must_use]
+    pub fn borrow(&self) -> Res<'_, T> {
+        let trace = AccessTrace::new(SystemParamKind::Res(TypeData::new::<T>()));
+        Res(self.0.borrow(), trace)
+    }
+
+    /// Mutably borrows the shared value.
+    ///
+    /// Requires mutable access to the handle, so systems must request
+    /// `ResMut<SharedResource<T>>` to write to the value.
+    #[must_use]
+    pub fn borrow_mut(&mut self) -> ResMut<'_, T> {
+        let trace = AccessTrace::new(SystemParamKind::ResMut(TypeData::new::<T>()));
+        ResMut(self.0.borrow_mut(), trace)
+    }
+
+    /// Returns whether both handles point to the same value.
+    #[inline]
+    #[must_use]
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T> Clone for SharedResource<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> fmt::Debug for SharedResource<T>
+where
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple(stringify!(SharedResource))
+            .field(&self.0)
+            .finish()
+    }
+}
@@ -0,0 +1,20 @@
+use crate::resource::Resource;
+use rustc_hash::FxHashMap;
+use std::any::TypeId;
+use std::fmt;
+
+/// Copy of all cloneable resources in a [`ResourceStorage`](crate::resource::ResourceStorage).
+///
+/// Created with [`ResourceStorage::snapshot`](crate::resource::ResourceStorage::snapshot) and
+/// applied with [`ResourceStorage::restore`](crate::resource::ResourceStorage::restore).
+pub struct ResourceSnapshot {
+    pub(crate) resources: FxHashMap<TypeId, Box<dyn Resource>>,
+}
+
+impl fmt::Debug for ResourceSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(stringify!(ResourceSnapshot))
+            .field("type_ids", &self.resources.keys())
+            .finish_non_exhaustive()
+    }
+}
@@ -4,6 +4,9 @@ use crate::system::SystemParam;
 use crate::World;
 
 /// Helper trait for borrowing data from a registry.
+///
+/// Custom parameters may borrow mutable views such as [`CompMut`], as long as their
+/// [`KIND`](SystemParam::KIND) reports the data they borrow.
 pub trait SystemBorrow<TRegistry = World>: SystemParam {
     /// Borrows data from the given `registry`.
     #[must_use]
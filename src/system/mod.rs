@@ -2,24 +2,34 @@
 
 mod borrow;
 mod param;
+mod param_set;
 mod run;
 
 pub use self::borrow::*;
 pub use self::param::*;
+pub use self::param_set::*;
 pub use self::run::*;
 
 use crate::entity::EntityStorage;
 use crate::resource::ResourceStorage;
 use crate::World;
+use std::borrow::Cow;
 
 /// Encapsulates a function that borrows data from a registry during execution.
+///
+/// Systems are boxed so they can be stored and run repeatedly, which requires the function to
+/// be `'static`. To run a closure that borrows local data, call `run` on the registry directly.
 pub struct System<TRegistry = World, TReturn = ()> {
     system_fn: Box<dyn FnMut(&TRegistry) -> TReturn + Send + Sync + 'static>,
-    params: &'static [SystemParamKind],
+    params: Cow<'static, [SystemParamKind]>,
 }
 
 impl<TRegistry, TReturn> System<TRegistry, TReturn> {
     /// Creates a new system from the given runnable function.
+    ///
+    /// The function may capture state, which is owned by the system and persists between runs.
+    /// To create many similar systems, return them from a function that takes the state to
+    /// capture as an argument.
     #[must_use]
     pub fn new<TParams>(f: impl IntoSystem<TRegistry, TParams, TReturn>) -> Self {
         f.system()
@@ -33,7 +43,39 @@ impl<TRegistry, TReturn> System<TRegistry, TReturn> {
     /// Returns the data that the system borrows from the registry during execution.
     #[must_use]
     pub fn params(&self) -> &[SystemParamKind] {
-        self.params
+        &self.params
+    }
+}
+
+impl<TRegistry, TReturn> System<TRegistry, TReturn>
+where
+    TRegistry: 'static,
+    TReturn: 'static,
+{
+    /// Creates a new system that runs `self` and passes its output as [`In`] to `f`.
+    ///
+    /// The parameters of the new system are the union of the parameters of both functions.
+    #[must_use]
+    pub fn pipe<TParams, TOutput, F>(mut self, mut f: F) -> System<TRegistry, TOutput>
+    where
+        F: RunWithInput<TRegistry, TReturn, TParams, TOutput> + Send + Sync + 'static,
+        for<'a> &'a mut F: RunWithInput<TRegistry, TReturn, TParams, TOutput>,
+    {
+        let mut params = self.params.into_owned();
+
+        for &param in F::PARAMS {
+            if !params.contains(&param) {
+                params.push(param);
+            }
+        }
+
+        System {
+            system_fn: Box::new(move |registry: &TRegistry| {
+                let input = (self.system_fn)(registry);
+                RunWithInput::run_with_input(&mut f, input, registry)
+            }),
+            params: Cow::Owned(params),
+        }
     }
 }
 
@@ -64,7 +106,7 @@ macro_rules! impl_into_system_in {
                     system_fn: Box::new(move |$registry: &$Registry| {
                         Run::run(&mut self, $registry)
                     }),
-                    params: TFunc::PARAMS,
+                    params: Cow::Borrowed(TFunc::PARAMS),
                 }
             }
         }
@@ -4,6 +4,7 @@ use crate::util::TypeData;
 
 /// The kind of data that can be borrowed from a registry.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
 pub enum SystemParamKind {
     /// View over all entities in an [`EntityStorage`](crate::entity::EntityStorage).
     Entities,
@@ -15,6 +16,9 @@ pub enum SystemParamKind {
     Res(TypeData),
     /// Exclusive view over a resource of a given type.
     ResMut(TypeData),
+    /// Set of parameters that are borrowed one at a time. Conflicts with anything that conflicts
+    /// with one of its members. Members within one set are not checked against each other.
+    ParamSet(&'static [SystemParamKind]),
 }
 
 impl SystemParamKind {
@@ -31,6 +35,9 @@ impl SystemParamKind {
             (Self::Res(r1), Self::ResMut(r2)) => r1 == r2,
             (Self::ResMut(r1), Self::Res(r2)) => r1 == r2,
             (Self::ResMut(r1), Self::ResMut(r2)) => r1 == r2,
+            (Self::ParamSet(set), other) | (other, Self::ParamSet(set)) => {
+                set.iter().any(|&param| param.conflicts_with(other))
+            }
             _ => false,
         }
     }
@@ -0,0 +1,76 @@
+use crate::system::{SystemBorrow, SystemParam, SystemParamKind};
+use crate::World;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Set of system parameters that may conflict with each other, such as two [`CompMut`] of the
+/// same component type.
+///
+/// Members are borrowed on demand through accessors like [`p0`](ParamSet::p0) and only one can
+/// be accessed at a time.
+///
+/// [`CompMut`]: crate::entity::CompMut
+pub struct ParamSet<'a, T, TRegistry = World> {
+    registry: &'a TRegistry,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, TRegistry> fmt::Debug for ParamSet<'_, T, TRegistry> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParamSet").finish_non_exhaustive()
+    }
+}
+
+macro_rules! impl_param_set {
+    ($(($Param:ident, $param:ident)),+) => {
+        impl<'a, TRegistry, $($Param),+> ParamSet<'a, ($($Param,)+), TRegistry>
+        where
+            $($Param: SystemBorrow<TRegistry>,)+
+        {
+            $(
+                #[doc = concat!("Borrows the `", stringify!($Param), "` member of the set.")]
+                #[must_use]
+                pub fn $param(&mut self) -> $Param::Param<'_> {
+                    $Param::borrow(self.registry)
+                }
+            )+
+        }
+
+        impl<TRegistry, $($Param),+> SystemParam for ParamSet<'_, ($($Param,)+), TRegistry>
+        where
+            TRegistry: 'static,
+            $($Param: SystemParam,)+
+        {
+            const KIND: SystemParamKind = SystemParamKind::ParamSet(&[$($Param::KIND),+]);
+
+            type Param<'a> = ParamSet<'a, ($($Param,)+), TRegistry>;
+        }
+
+        impl<TRegistry, $($Param),+> SystemBorrow<TRegistry>
+            for ParamSet<'_, ($($Param,)+), TRegistry>
+        where
+            TRegistry: 'static,
+            $($Param: SystemBorrow<TRegistry>,)+
+        {
+            fn borrow(registry: &TRegistry) -> Self::Param<'_> {
+                ParamSet {
+                    registry,
+                    _phantom: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+mod impls {
+    use super::*;
+
+    impl_param_set!((P0, p0), (P1, p1));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2), (P3, p3));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2), (P3, p3), (P4, p4));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2), (P3, p3), (P4, p4), (P5, p5));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2), (P3, p3), (P4, p4), (P5, p5), (P6, p6));
+    impl_param_set!((P0, p0), (P1, p1), (P2, p2), (P3, p3), (P4, p4), (P5, p5), (P6, p6), (P7, p7));
+}
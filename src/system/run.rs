@@ -12,8 +12,27 @@ pub trait Run<TRegistry, TParams, TReturn> {
     fn run(self, registry: &TRegistry) -> TReturn;
 }
 
+/// Trait implemented by functions that take an input and borrow data from a registry.
+pub trait RunWithInput<TRegistry, TInput, TParams, TReturn> {
+    /// The data that the system borrows from the registry during execution
+    const PARAMS: &'static [SystemParamKind];
+
+    /// Runs the function with `input` in the given `registry`.
+    fn run_with_input(self, input: TInput, registry: &TRegistry) -> TReturn;
+}
+
+/// Input passed to a function as its first parameter.
+///
+/// Used by systems that consume the output of another system via
+/// [`System::pipe`](crate::system::System::pipe).
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct In<T>(pub T);
+
 impl World {
     /// Runs the function with data borrowed from `self`.
+    ///
+    /// Unlike [`System`](crate::system::System), the function is not required to be `'static`
+    /// and may borrow local data.
     pub fn run<TParams, TReturn>(&self, f: impl Run<Self, TParams, TReturn>) -> TReturn {
         Run::run(f, self)
     }
@@ -56,6 +75,21 @@ macro_rules! impl_run_in {
                 self($($Param::borrow($registry),)*)
             }
         }
+
+        impl<TFunc, TInput, $($Param,)* TReturn>
+            RunWithInput<$Registry, TInput, ($($Param,)*), TReturn> for TFunc
+        where
+            TFunc: FnOnce(In<TInput>, $($Param),*) -> TReturn
+                 + FnOnce(In<TInput>, $(<$Param as SystemParam>::Param<'_>),*) -> TReturn,
+            $($Param: SystemBorrow<$Registry>,)*
+        {
+            const PARAMS: &'static [SystemParamKind] = &[$($Param::KIND),*];
+
+            #[allow(unused_variables)]
+            fn run_with_input(self, input: TInput, $registry: &$Registry) -> TReturn {
+                self(In(input), $($Param::borrow($registry),)*)
+            }
+        }
     };
 }
 
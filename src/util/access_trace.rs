@@ -0,0 +1,145 @@
+use crate::system::SystemParamKind;
+
+#[cfg(feature = "trace")]
+pub(crate) use self::hook::collect_accesses;
+#[cfg(feature = "trace")]
+pub use self::hook::{clear_access_hook, set_access_hook, AccessEvent, AccessLog};
+
+/// Reports a borrow when created and the matching release when dropped. Zero-sized when the
+/// `trace` feature is disabled.
+pub(crate) struct AccessTrace {
+    #[cfg(feature = "trace")]
+    kind: SystemParamKind,
+}
+
+impl AccessTrace {
+    #[inline]
+    #[must_use]
+    pub(crate) fn new(kind: SystemParamKind) -> Self {
+        #[cfg(feature = "trace")]
+        {
+            hook::emit(AccessEvent::Borrow(kind));
+            Self { kind }
+        }
+
+        #[cfg(not(feature = "trace"))]
+        {
+            let _ = kind;
+            Self {}
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for AccessTrace {
+    #[inline]
+    fn drop(&mut self) {
+        hook::emit(AccessEvent::Release(self.kind));
+    }
+}
+
+#[cfg(feature = "trace")]
+mod hook {
+    use crate::system::SystemParamKind;
+    use std::cell::RefCell;
+    use std::sync::atomic::{AtomicPtr, Ordering};
+    use std::{mem, ptr};
+
+    static ACCESS_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+    thread_local! {
+        static COLLECTOR: RefCell<Option<Vec<SystemParamKind>>> = const { RefCell::new(None) };
+    }
+
+    /// Component or resource access reported to the access hook.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub enum AccessEvent {
+        /// The data was borrowed.
+        Borrow(SystemParamKind),
+        /// The data was released.
+        Release(SystemParamKind),
+    }
+
+    /// Sets a global hook called on every component and resource borrow and release.
+    ///
+    /// Useful for finding the access sequence that led to a borrow panic.
+    pub fn set_access_hook(hook: fn(AccessEvent)) {
+        ACCESS_HOOK.store(hook as *mut (), Ordering::Release);
+    }
+
+    /// Removes the global access hook, if any.
+    pub fn clear_access_hook() {
+        ACCESS_HOOK.store(ptr::null_mut(), Ordering::Release);
+    }
+
+    /// Component and resource borrows collected by
+    /// [`World::with_access_trace`](crate::World::with_access_trace).
+    #[derive(Clone, Default, PartialEq, Eq, Hash, Debug)]
+    pub struct AccessLog {
+        accesses: Vec<SystemParamKind>,
+    }
+
+    impl AccessLog {
+        /// Returns all borrows in the order they were performed.
+        #[inline]
+        #[must_use]
+        pub fn accesses(&self) -> &[SystemParamKind] {
+            &self.accesses
+        }
+
+        /// Returns whether `kind` was borrowed at least once.
+        #[inline]
+        #[must_use]
+        pub fn contains(&self, kind: SystemParamKind) -> bool {
+            self.accesses.contains(&kind)
+        }
+    }
+
+    /// Restores the collector of the enclosing scope, even if the traced function panics.
+    struct CollectorGuard {
+        outer: Option<Vec<SystemParamKind>>,
+    }
+
+    impl Drop for CollectorGuard {
+        fn drop(&mut self) {
+            COLLECTOR.with_borrow_mut(|collector| {
+                let inner = mem::replace(collector, self.outer.take());
+
+                // Borrows in nested scopes are also borrows of the enclosing scope.
+                if let (Some(outer), Some(inner)) = (collector.as_mut(), inner) {
+                    outer.extend(inner);
+                }
+            });
+        }
+    }
+
+    pub(crate) fn collect_accesses(f: impl FnOnce()) -> AccessLog {
+        let outer = COLLECTOR.with_borrow_mut(|collector| collector.replace(Vec::new()));
+        let guard = CollectorGuard { outer };
+
+        f();
+
+        let accesses = COLLECTOR.with_borrow(|collector| collector.clone().unwrap_or_default());
+        drop(guard);
+
+        AccessLog { accesses }
+    }
+
+    pub fn emit(event: AccessEvent) {
+        let hook = ACCESS_HOOK.load(Ordering::Acquire);
+
+        if !hook.is_null() {
+            let hook = unsafe { mem::transmute::<*mut (), fn(AccessEvent)>(hook) };
+            hook(event);
+        }
+
+        if let AccessEvent::Borrow(kind) = event {
+            // The collector may already be destroyed if the thread is exiting.
+            let _ = COLLECTOR.try_with(|collector| {
+                if let Some(accesses) = collector.borrow_mut().as_mut() {
+                    accesses.push(kind);
+                }
+            });
+        }
+    }
+}
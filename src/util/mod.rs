@@ -1,5 +1,14 @@
 //! Various utilities.
 
+mod access_trace;
+mod secondary_map;
 mod type_data;
 
+#[cfg(feature = "trace")]
+pub use self::access_trace::{clear_access_hook, set_access_hook, AccessEvent, AccessLog};
+pub use self::secondary_map::*;
 pub use self::type_data::*;
+
+#[cfg(feature = "trace")]
+pub(crate) use self::access_trace::collect_accesses;
+pub(crate) use self::access_trace::AccessTrace;
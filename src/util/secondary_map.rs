@@ -0,0 +1,194 @@
+use crate::entity::{DenseEntity, Entity, SparseVec};
+use std::{fmt, mem};
+
+/// Map from entities to values of type `V`, stored outside of an
+/// [`EntityStorage`](crate::entity::EntityStorage).
+///
+/// Lookups go through a sparse array indexed by entity, like component storages, and values are
+/// packed in a dense array. Entities with the same index but a different version are treated as
+/// different keys.
+///
+/// To remove values when their entities are destroyed, call [`remove`](Self::remove) from a
+/// callback registered with
+/// [`EntityStorage::on_destroy`](crate::entity::EntityStorage::on_destroy). Alternatively, use
+/// [`retain`](Self::retain) with
+/// [`EntityStorage::contains`](crate::entity::EntityStorage::contains) to remove stale entries.
+pub struct SecondaryMap<V> {
+    sparse: SparseVec,
+    entities: Vec<Entity>,
+    values: Vec<V>,
+}
+
+impl<V> SecondaryMap<V> {
+    /// Creates a new, empty map.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sparse: SparseVec::new(),
+            entities: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Maps `entity` to `value`.
+    ///
+    /// Returns the value previously mapped to `entity`, if any. Values mapped to an entity with
+    /// the same index but a different version are dropped.
+    pub fn insert(&mut self, entity: Entity, value: V) -> Option<V> {
+        let dense_entity = self.sparse.get_mut_or_allocate_at(entity.sparse());
+
+        match dense_entity {
+            Some(dense_entity) => {
+                let index = dense_entity.dense();
+                let prev_entity = mem::replace(&mut self.entities[index], entity);
+                let prev_value = mem::replace(&mut self.values[index], value);
+
+                if prev_entity.version == entity.version {
+                    Some(prev_value)
+                } else {
+                    dense_entity.version = entity.version;
+                    None
+                }
+            }
+            None => {
+                *dense_entity = Some(DenseEntity {
+                    index: self.entities.len() as u32,
+                    version: entity.version,
+                });
+
+                self.entities.push(entity);
+                self.values.push(value);
+                None
+            }
+        }
+    }
+
+    /// Removes the value mapped to `entity`.
+    ///
+    /// Returns the removed value, if any.
+    pub fn remove(&mut self, entity: Entity) -> Option<V> {
+        let index = self.sparse.remove(entity)?.dense();
+
+        self.entities.swap_remove(index);
+        let value = self.values.swap_remove(index);
+
+        if let Some(&moved_entity) = self.entities.get(index) {
+            unsafe {
+                *self.sparse.get_unchecked_mut(moved_entity.sparse()) = Some(DenseEntity {
+                    index: index as u32,
+                    version: moved_entity.version,
+                });
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Returns a reference to the value mapped to `entity`, if any.
+    #[must_use]
+    pub fn get(&self, entity: Entity) -> Option<&V> {
+        let index = self.sparse.get(entity)?.dense();
+        Some(&self.values[index])
+    }
+
+    /// Returns a mutable reference to the value mapped to `entity`, if any.
+    #[must_use]
+    pub fn get_mut(&mut self, entity: Entity) -> Option<&mut V> {
+        let index = self.sparse.get(entity)?.dense();
+        Some(&mut self.values[index])
+    }
+
+    /// Returns whether `entity` is mapped to a value.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.sparse.contains(entity)
+    }
+
+    /// Removes all values for which `f` returns `false`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Entity, &mut V) -> bool,
+    {
+        let mut index = 0;
+
+        while let Some(&entity) = self.entities.get(index) {
+            if f(entity, &mut self.values[index]) {
+                index += 1;
+            } else {
+                self.remove(entity);
+            }
+        }
+    }
+
+    /// Returns an iterator over all entities and their values, in unspecified order.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &V)> + '_ {
+        self.entities.iter().copied().zip(&self.values)
+    }
+
+    /// Returns an iterator over all entities and mutable references to their values, in
+    /// unspecified order.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut V)> + '_ {
+        self.entities.iter().copied().zip(&mut self.values)
+    }
+
+    /// Returns all entities in the map as a slice.
+    #[inline]
+    #[must_use]
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns all values in the map as a slice.
+    #[inline]
+    #[must_use]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns all values in the map as a mutable slice.
+    #[inline]
+    #[must_use]
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// Returns the number of values in the map.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether the map contains no values.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Removes all values from the map.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.sparse.clear();
+        self.entities.clear();
+        self.values.clear();
+    }
+}
+
+impl<V> Default for SecondaryMap<V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> fmt::Debug for SecondaryMap<V>
+where
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
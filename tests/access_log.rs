@@ -0,0 +1,75 @@
+//! Tests for collecting the borrows performed within a scope.
+
+#![cfg(feature = "trace")]
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::system::SystemParamKind;
+use sparsey::util::TypeData;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn test_with_access_trace() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.resources.insert(C(0));
+
+    let a = SystemParamKind::Comp(TypeData::new::<A>());
+    let b = SystemParamKind::CompMut(TypeData::new::<B>());
+    let c = SystemParamKind::Res(TypeData::new::<C>());
+
+    let mut system = System::new(|a: Comp<A>, mut b: CompMut<B>, c: Res<C>| {
+        for (a, b) in (&a, &mut b).iter() {
+            b.0 = a.0 + c.0;
+        }
+    });
+
+    let log = world.with_access_trace(|world| system.run(world));
+    assert_eq!(log.accesses(), [a, b, c]);
+
+    // Nested scopes are also recorded by the enclosing scope
+    let mut inner = None;
+
+    let log = world.with_access_trace(|world| {
+        let _ = world.resources.borrow::<C>();
+
+        inner = Some(world.with_access_trace(|world| {
+            let _ = world.entities.borrow::<A>();
+        }));
+    });
+
+    assert_eq!(inner.unwrap().accesses(), [a]);
+    assert_eq!(log.accesses(), [c, a]);
+    assert!(!log.contains(b));
+
+    // Borrows outside of a scope are not recorded
+    let _ = world.entities.borrow_mut::<B>();
+    assert!(world.with_access_trace(|_| ()).accesses().is_empty());
+}
+
+#[test]
+fn test_with_access_trace_failed_borrow() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.resources.insert(B(0));
+
+    let a = SystemParamKind::CompMut(TypeData::new::<A>());
+    let b = SystemParamKind::ResMut(TypeData::new::<B>());
+
+    // Borrows that panic are not recorded
+    let log = world.with_access_trace(|world| {
+        let _a = world.entities.borrow_mut::<A>();
+        let _b = world.resources.borrow_mut::<B>();
+
+        let borrow_a = || drop(world.entities.borrow::<A>());
+        assert!(panic::catch_unwind(AssertUnwindSafe(borrow_a)).is_err());
+
+        let borrow_b = || drop(world.resources.borrow::<B>());
+        assert!(panic::catch_unwind(AssertUnwindSafe(borrow_b)).is_err());
+    });
+
+    assert_eq!(log.accesses(), [a, b]);
+}
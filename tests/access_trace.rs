@@ -0,0 +1,54 @@
+//! Tests for the access tracing hook.
+
+#![cfg(feature = "trace")]
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::system::SystemParamKind;
+use sparsey::util::{clear_access_hook, set_access_hook, AccessEvent, TypeData};
+use std::sync::Mutex;
+
+static EVENTS: Mutex<Vec<AccessEvent>> = Mutex::new(Vec::new());
+
+fn record(event: AccessEvent) {
+    EVENTS.lock().unwrap().push(event);
+}
+
+#[test]
+fn test_access_trace() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.resources.insert(C(0));
+
+    set_access_hook(record);
+
+    world.run(|_: Comp<A>, _: CompMut<B>, _: ResMut<C>| ());
+
+    clear_access_hook();
+
+    let a = SystemParamKind::Comp(TypeData::new::<A>());
+    let b = SystemParamKind::CompMut(TypeData::new::<B>());
+    let c = SystemParamKind::ResMut(TypeData::new::<C>());
+
+    let events = EVENTS.lock().unwrap().clone();
+    assert_eq!(events.len(), 6);
+    assert_eq!(
+        &events[..3],
+        &[
+            AccessEvent::Borrow(a),
+            AccessEvent::Borrow(b),
+            AccessEvent::Borrow(c),
+        ],
+    );
+
+    for kind in [a, b, c] {
+        assert!(events[3..].contains(&AccessEvent::Release(kind)));
+    }
+
+    // No events after clearing the hook
+    world.run(|_: Comp<A>| ());
+    assert_eq!(EVENTS.lock().unwrap().len(), 6);
+}
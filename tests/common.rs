@@ -1,14 +1,23 @@
+//! Components shared by the integration tests.
+
+#![allow(dead_code)]
+
+/// Test component A.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct A(pub u32);
 
+/// Test component B.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct B(pub u32);
 
+/// Test component C.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct C(pub u32);
 
+/// Test component D.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct D(pub u32);
 
+/// Test component E.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct E(pub u32);
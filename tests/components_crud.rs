@@ -1,3 +1,5 @@
+//! Tests for adding and removing components.
+
 mod common;
 
 use common::*;
@@ -69,3 +71,350 @@ fn test_components_crud() {
         assert_eq!(a.get(e0), None);
     });
 }
+
+#[test]
+fn test_components_create_from() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+
+    let template = (A(0), B(0));
+    let e0 = entities.create_from(&template);
+    let e1 = entities.create_from(&template);
+    assert_ne!(e0, e1);
+
+    // Entities created from the same template have independent components
+    entities.run(|mut a: CompMut<A>, b: Comp<B>| {
+        a[e0].0 = 1;
+
+        assert_eq!(a.get(e0), Some(&A(1)));
+        assert_eq!(a.get(e1), Some(&A(0)));
+        assert_eq!(b.get(e0), Some(&B(0)));
+        assert_eq!(b.get(e1), Some(&B(0)));
+    });
+
+    // The template is left untouched
+    assert_eq!(template, (A(0), B(0)));
+}
+
+#[test]
+fn test_components_zip_by_entity() {
+    let mut server = EntityStorage::default();
+    server.register::<A>();
+
+    let e0 = server.create((A(0),));
+    let e1 = server.create((A(1),));
+    let e2 = server.create((A(2),));
+
+    // The client mirrors a subset of the server entities
+    let mut client = EntityStorage::default();
+    client.register::<B>();
+    client.extend(server.entities().iter().map(|_| (B(0),)));
+    client.run(|mut b: CompMut<B>| {
+        b[e0].0 = 10;
+        b[e2].0 = 12;
+    });
+    client.destroy(e1);
+
+    let a = server.borrow::<A>();
+    let b = client.borrow::<B>();
+
+    let zipped = a.zip_by_entity(&b).collect::<Vec<_>>();
+    assert_eq!(zipped, [(e0, &A(0), &B(10)), (e2, &A(2), &B(12))]);
+
+    // No overlap
+    let mut other = EntityStorage::default();
+    other.register::<B>();
+    let b = other.borrow::<B>();
+    assert_eq!(a.zip_by_entity(&b).count(), 0);
+}
+
+#[test]
+fn test_components_sample() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.extend((0..100).map(|i| (A(i),)));
+
+    let a = entities.borrow::<A>();
+
+    // Sample size is min(n, len)
+    assert_eq!(a.sample(10).count(), 10);
+    assert_eq!(a.sample(0).count(), 0);
+    assert_eq!(a.sample(1000).count(), 100);
+
+    // Samples contain no duplicates and match their entities
+    let sample = a.sample(50).collect::<Vec<_>>();
+    assert!(sample.windows(2).all(|w| w[0].1 < w[1].1));
+    assert!(sample
+        .iter()
+        .all(|&(entity, component)| a.get(entity) == Some(component)));
+
+    // Samples are deterministic under a fixed seed
+    let s0 = a.sample_seeded(10, 42).collect::<Vec<_>>();
+    let s1 = a.sample_seeded(10, 42).collect::<Vec<_>>();
+    assert_eq!(s0, s1);
+}
+
+#[test]
+fn test_components_set_if_neq() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    let e0 = entities.create((A(0),));
+    let e1 = entities.create(());
+
+    entities.run(|mut a: CompMut<A>| {
+        assert!(!a.set_if_neq(e0, A(0)));
+        assert!(a.set_if_neq(e0, A(1)));
+        assert_eq!(a[e0], A(1));
+
+        // Missing components are not inserted
+        assert!(!a.set_if_neq(e1, A(1)));
+        assert!(!a.contains(e1));
+    });
+}
+
+#[test]
+fn test_components_take_component() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1), B(1)));
+
+    // Get present and absent components
+    assert_eq!(entities.get_component::<A>(e0), Some(&A(0)));
+    assert_eq!(entities.get_component::<C>(e0), None);
+
+    *entities.get_component_mut::<A>(e0).unwrap() = A(10);
+    assert_eq!(entities.get_component::<A>(e0), Some(&A(10)));
+
+    // Take present and absent components
+    assert_eq!(entities.take_component::<A>(e0), Some(A(10)));
+    assert_eq!(entities.take_component::<A>(e0), None);
+    assert_eq!(entities.get_component_mut::<A>(e0), None);
+
+    // The group is updated on removal
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!((&a, &b).group_entities(), Some([e1].as_slice()));
+        assert!(b.contains(e0));
+    });
+}
+
+#[test]
+fn test_components_for_each_with_others() {
+    /// Cell storing its value and the sum of its neighbors.
+    struct Cell {
+        value: u32,
+        neighbor_sum: u32,
+    }
+
+    let mut entities = EntityStorage::default();
+    entities.register::<Cell>();
+
+    let e = entities
+        .extend((0..5).map(|value| {
+            (Cell {
+                value,
+                neighbor_sum: 0,
+            },)
+        }))
+        .to_vec();
+
+    entities.run(|mut cells: CompMut<Cell>| {
+        cells.for_each_with_others(|entity, cell, others| {
+            // The current entity can't be accessed through the view
+            assert!(others.get(entity).is_none());
+            assert_eq!(others.len(), 4);
+            assert_eq!(others.iter().count(), 4);
+
+            let i = e.iter().position(|&n| n == entity).unwrap();
+            let left = i.checked_sub(1).and_then(|i| others.get(e[i]));
+            let right = e.get(i + 1).and_then(|&n| others.get(n));
+
+            cell.neighbor_sum = left.map_or(0, |c| c.value) + right.map_or(0, |c| c.value);
+        });
+
+        let sums = e.iter().map(|&e| cells[e].neighbor_sum).collect::<Vec<_>>();
+        assert_eq!(sums, [1, 2, 4, 6, 3]);
+    });
+}
+
+#[test]
+fn test_components_drain() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1), B(1)));
+    let e2 = entities.create((A(2),));
+
+    // Partial drain only removes the yielded components
+    let drained = entities.drain::<A>().take(2).collect::<Vec<_>>();
+    assert_eq!(drained, [(e2, A(2)), (e1, A(1))]);
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!(a.entities(), &[e0]);
+        assert_eq!((&a, &b).group_entities(), Some([e0].as_slice()));
+    });
+
+    // Full drain leaves the storage empty
+    let mut drain = entities.drain::<A>();
+    assert_eq!(drain.len(), 1);
+    assert_eq!(drain.next(), Some((e0, A(0))));
+    assert_eq!(drain.next(), None);
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert!(a.is_empty());
+        assert_eq!(b.len(), 2);
+        assert_eq!((&a, &b).group_entities(), Some([].as_slice()));
+    });
+
+    // Regrouping works after draining
+    entities.insert(e1, (A(10),));
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!((&a, &b).group_entities(), Some([e1].as_slice()));
+    });
+}
+
+#[test]
+fn test_components_drain_leaked() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1), B(1)));
+    entities.create((A(2), B(2)));
+
+    // Leaking a partially consumed drain keeps the groups in bounds
+    let mut drain = entities.drain::<A>();
+    drain.next();
+    let _ = Box::leak(Box::new(drain));
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!((&a, &b).group_entities(), Some([e0, e1].as_slice()));
+        assert_eq!((&a, &b).iter().count(), 2);
+    });
+
+    // Same, when the drain is leaked after removing everything
+    let mut drain = entities.drain::<A>();
+    drain.by_ref().for_each(drop);
+    let _ = Box::leak(Box::new(drain));
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!((&a, &b).group_entities(), Some([].as_slice()));
+        assert_eq!((&a, &b).iter().count(), 0);
+    });
+}
+
+#[test]
+fn test_components_has_components() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1),));
+    let e2 = entities.create(());
+
+    // All components present
+    assert!(entities.has_components::<(A, B)>(e0));
+    assert!(entities.has_any_components::<(A, B)>(e0));
+
+    // Some components present
+    assert!(!entities.has_components::<(A, B)>(e1));
+    assert!(entities.has_any_components::<(A, B)>(e1));
+
+    // No components present
+    assert!(!entities.has_components::<(A, B)>(e2));
+    assert!(!entities.has_any_components::<(A, B)>(e2));
+
+    // Unregistered components are absent
+    assert!(!entities.has_components::<(A, C)>(e0));
+    assert!(entities.has_any_components::<(A, C)>(e0));
+}
+
+#[test]
+fn test_components_entities_with() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1),));
+    entities.create((B(2),));
+
+    let with_a = entities.entities_with::<A>().to_vec();
+    assert_eq!(with_a.len(), 2);
+    assert!(with_a.contains(&e0));
+    assert!(with_a.contains(&e1));
+
+    entities.run(|a: Comp<A>| {
+        assert_eq!(with_a.len(), a.len());
+        assert!(with_a.iter().all(|&entity| a.contains(entity)));
+    });
+
+    // Unregistered components have no entities
+    assert!(entities.entities_with::<C>().is_empty());
+}
+
+#[test]
+fn test_components_get_or_insert_with() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+
+    let e0 = entities.create((A(0),));
+    let e1 = entities.create((A(1), B(1)));
+
+    // Insert path
+    let b = entities.get_component_or_insert_with(e0, || B(10));
+    assert_eq!(b, Some(&mut B(10)));
+
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        let mut grouped = (&a, &b).group_entities().unwrap().to_vec();
+        grouped.sort_unstable();
+        assert_eq!(grouped, [e0, e1]);
+    });
+
+    // Already present path
+    let b = entities.get_component_or_insert_with(e1, || B(20)).unwrap();
+    assert_eq!(*b, B(1));
+    b.0 = 2;
+    assert_eq!(entities.get_component::<B>(e1), Some(&B(2)));
+
+    // Missing entity
+    entities.destroy(e1);
+    assert_eq!(entities.get_component_or_insert_with(e1, || B(30)), None);
+    assert_eq!(entities.get_component::<B>(e1), None);
+}
+
+#[test]
+fn test_components_split_dense() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    let e = entities.extend((0..5).map(|i| (A(i),))).to_vec();
+    entities.delete::<(A,)>(e[1]);
+
+    entities.run(|mut a: CompMut<A>| {
+        // Entities and components are index-aligned
+        let (a_entities, components) = a.split_dense_mut();
+        assert_eq!(a_entities.len(), components.len());
+
+        for (&entity, component) in a_entities.iter().zip(components) {
+            assert_eq!(entity, e[component.0 as usize]);
+            component.0 *= 10;
+        }
+    });
+
+    entities.run(|a: Comp<A>| {
+        let (a_entities, components) = a.split_dense();
+        assert_eq!(a_entities.len(), 4);
+
+        for (&entity, component) in a_entities.iter().zip(components) {
+            assert_eq!(&a[entity], component);
+            assert_eq!(entity, e[component.0 as usize / 10]);
+        }
+    });
+}
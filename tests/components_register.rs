@@ -1,6 +1,9 @@
+//! Tests for registering component types.
+
 mod common;
 
 use self::common::*;
+use sparsey::entity::BorrowError;
 use sparsey::prelude::*;
 
 #[test]
@@ -30,3 +33,86 @@ fn test_components_register() {
     assert!(entities.is_registered::<A>());
     assert!(entities.is_registered::<B>());
 }
+
+#[test]
+fn test_components_try_create() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    // Create entity with registered components
+    let e0 = entities.try_create((A(0),)).unwrap();
+    assert!(entities.contains(e0));
+
+    // Try to create entity with unregistered components
+    let error = entities.try_create((A(1), B(1))).unwrap_err();
+    assert_eq!(error.type_name(), std::any::type_name::<B>());
+    assert_eq!(entities.entities(), &[e0]);
+
+    // Try to insert unregistered components
+    let error = entities.try_insert(e0, (B(0),)).unwrap_err();
+    assert_eq!(error.type_name(), std::any::type_name::<B>());
+
+    // Insert components after registering them
+    entities.register::<B>();
+    assert_eq!(entities.try_insert(e0, (B(0),)), Ok(true));
+    assert!(entities.borrow::<B>().contains(e0));
+
+    // Insert components into missing entity
+    entities.destroy(e0);
+    assert_eq!(entities.try_insert(e0, (B(0),)), Ok(false));
+}
+
+#[test]
+fn test_components_register_bundle() {
+    let mut entities = EntityStorage::default();
+    entities.register::<B>();
+
+    // Only new components are counted
+    assert_eq!(entities.register_bundle::<(A, B, C)>(), 2);
+    assert!(entities.is_registered::<A>());
+    assert!(entities.is_registered::<B>());
+    assert!(entities.is_registered::<C>());
+    assert!(!entities.is_registered::<D>());
+
+    assert_eq!(entities.register_bundle::<(A, B, C)>(), 0);
+    assert_eq!(entities.register_bundle::<()>(), 0);
+
+    let e0 = entities.create((A(0), B(0), C(0)));
+    assert!((&entities.borrow::<A>(), &entities.borrow::<C>()).matches(e0));
+}
+
+#[test]
+fn test_components_try_borrow() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    // Unregistered components
+    let error = entities.try_borrow::<B>().err().unwrap();
+    assert!(matches!(error, BorrowError::Unregistered(_)));
+    assert_eq!(error.type_name(), std::any::type_name::<B>());
+    assert!(matches!(
+        entities.try_borrow_mut::<B>().err(),
+        Some(BorrowError::Unregistered(_)),
+    ));
+
+    // Shared borrows don't conflict
+    let a = entities.try_borrow::<A>().unwrap();
+    assert!(entities.try_borrow::<A>().is_ok());
+
+    // Already borrowed
+    assert!(matches!(
+        entities.try_borrow_mut::<A>().err(),
+        Some(BorrowError::AlreadyBorrowed(_)),
+    ));
+
+    drop(a);
+    let a = entities.try_borrow_mut::<A>().unwrap();
+
+    assert!(matches!(
+        entities.try_borrow::<A>().err(),
+        Some(BorrowError::AlreadyBorrowed(_)),
+    ));
+
+    drop(a);
+    assert!(entities.try_borrow::<A>().is_ok());
+}
@@ -0,0 +1,40 @@
+//! Tests for reading from a world on multiple threads.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use std::thread;
+
+#[test]
+fn test_concurrent_read() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.resources.insert(C(3));
+
+    world.entities.extend((0..100).map(|i| (A(i), B(i * 2))));
+    world.entities.extend((0..50).map(|i| (A(i),)));
+
+    let sums = thread::scope(|scope| {
+        let handles = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    world.run(|a: Comp<A>, b: Comp<B>, c: Res<C>| {
+                        let mut sum = 0;
+                        (&a, &b).for_each(|(a, b)| sum += (a.0 + b.0) * c.0);
+                        sum
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    let expected = (0..100).map(|i| (i + i * 2) * 3).sum::<u32>();
+    assert_eq!(sums, [expected; 4]);
+}
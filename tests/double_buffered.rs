@@ -0,0 +1,53 @@
+//! Tests for double-buffered components.
+
+mod common;
+
+use common::*;
+use sparsey::entity::DoubleBuffered;
+use sparsey::prelude::*;
+
+#[test]
+fn test_double_buffered() {
+    let mut entities = EntityStorage::default();
+    entities.register_double_buffered::<A>();
+    entities.register::<B>();
+
+    let e0 = entities.create((DoubleBuffered::new(A(0)), B(0)));
+
+    // Both values are equal at creation
+    entities.run(|a: Comp<DoubleBuffered<A>>| {
+        assert_eq!(a.get_with_previous(e0), Some((&A(0), &A(0))));
+    });
+
+    for frame in 1..=3 {
+        entities.run(|mut a: CompMut<DoubleBuffered<A>>| {
+            a[e0].current_mut().0 = frame;
+
+            // Previous value is only updated on maintain
+            assert_eq!(a.get_with_previous(e0), Some((&A(frame), &A(frame - 1))));
+        });
+
+        entities.maintain();
+
+        entities.run(|a: Comp<DoubleBuffered<A>>| {
+            assert_eq!(a.get_with_previous(e0), Some((&A(frame), &A(frame))));
+        });
+    }
+
+    // Double buffering survives layout changes
+    let layout = GroupLayout::builder()
+        .add_group::<(DoubleBuffered<A>, B)>()
+        .build();
+
+    entities.set_layout(&layout);
+
+    entities.run(|mut a: CompMut<DoubleBuffered<A>>| {
+        a[e0].current_mut().0 = 10;
+    });
+
+    entities.maintain();
+
+    entities.run(|a: Comp<DoubleBuffered<A>>| {
+        assert_eq!(a[e0].previous(), &A(10));
+    });
+}
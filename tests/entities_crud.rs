@@ -1,3 +1,9 @@
+//! Tests for creating and destroying entities.
+
+mod common;
+
+use common::*;
+use sparsey::entity::{EntityLimitError, RecyclePolicy};
 use sparsey::prelude::*;
 
 #[test]
@@ -38,3 +44,242 @@ fn test_entities_crud() {
     assert!(!entities.contains(e1));
     assert_eq!(entities.entities(), &[]);
 }
+
+#[test]
+fn test_entities_reserve_shrink() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+
+    // Reserve capacity up front
+    entities.reserve(100);
+    assert!(entities.capacity() >= 100);
+    assert!(entities.borrow::<A>().capacity() >= 100);
+    assert!(entities.borrow::<C>().capacity() >= 100);
+
+    let created = entities
+        .extend((0..100).map(|i| (A(i), B(i), C(i))))
+        .to_vec();
+
+    assert_eq!(entities.entities().len(), 100);
+
+    // Shrinking keeps the contents
+    entities.run(|a: Comp<A>| {
+        created.iter().for_each(|&e| assert!(a.contains(e)));
+    });
+
+    for &entity in &created[10..] {
+        entities.destroy(entity);
+    }
+
+    entities.shrink_to_fit();
+    assert!(entities.capacity() >= 10);
+    assert!(entities.capacity() < 100);
+    assert_eq!(entities.entities().len(), 10);
+
+    entities.run(|a: Comp<A>, b: Comp<B>, c: Comp<C>| {
+        for (i, &entity) in created[..10].iter().enumerate() {
+            assert_eq!(a.get(entity), Some(&A(i as u32)));
+            assert_eq!(b.get(entity), Some(&B(i as u32)));
+            assert_eq!(c.get(entity), Some(&C(i as u32)));
+        }
+
+        assert_eq!((&a, &b).iter().count(), 10);
+        assert_eq!(c.capacity(), 10);
+    });
+
+    // Release everything after clearing
+    entities.clear();
+    entities.shrink_to_fit();
+    assert_eq!(entities.capacity(), 0);
+    assert_eq!(entities.borrow::<A>().capacity(), 0);
+
+    let e0 = entities.create((A(0), B(0), C(0)));
+    assert!(entities.contains(e0));
+}
+
+#[test]
+fn test_entities_recycle_policy() {
+    let mut entities = EntityStorage::default();
+    assert_eq!(entities.recycle_policy(), RecyclePolicy::Immediate);
+
+    // Immediate reuse
+    let e0 = entities.create(());
+    entities.destroy(e0);
+    let e1 = entities.create(());
+    assert_eq!(e1.index, e0.index);
+    assert_ne!(e1, e0);
+
+    // Deferred reuse
+    entities.set_recycle_policy(RecyclePolicy::Deferred(2));
+    entities.destroy(e1);
+
+    let e2 = entities.create(());
+    assert_ne!(e2.index, e1.index);
+
+    entities.maintain();
+    let e3 = entities.create(());
+    assert_ne!(e3.index, e1.index);
+
+    entities.maintain();
+    let e4 = entities.create(());
+    assert_eq!(e4.index, e1.index);
+    assert_ne!(e4, e1);
+    assert!(!entities.contains(e1));
+}
+
+#[test]
+fn test_entities_destroy_before_maintain() {
+    let mut entities = EntityStorage::default();
+
+    // Entities destroyed before maintain stay destroyed
+    let e0 = entities.create(());
+    let e1 = entities.create(());
+    entities.destroy(e0);
+    entities.maintain();
+    assert_eq!(entities.entities(), &[e1]);
+
+    let e2 = entities.create(());
+    entities.destroy(e2);
+    entities.maintain();
+    assert_eq!(entities.entities(), &[e1]);
+
+    // Same, when mixed with atomic creation
+    let e3 = entities.create_atomic();
+    let e4 = entities.create(());
+    let e5 = entities.create_atomic();
+    entities.destroy(e4);
+    entities.maintain();
+
+    let mut expected = vec![e1, e3, e5];
+    let mut actual = entities.entities().to_vec();
+    expected.sort();
+    actual.sort();
+    assert_eq!(actual, expected);
+
+    // Same, when the atomic entity uses a new index
+    let e6 = entities.create_atomic();
+    let e7 = entities.create(());
+    entities.destroy(e7);
+    entities.maintain();
+    assert!(entities.contains(e6));
+    assert!(!entities.contains(e7));
+}
+
+#[test]
+fn test_entities_destroy_all_with() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+
+    let e0 = entities.create((A(0), B(0), C(0)));
+    let e1 = entities.create((A(1), C(1)));
+    let e2 = entities.create((A(2), B(2)));
+    let e3 = entities.create((B(3),));
+
+    assert_eq!(entities.destroy_all_with::<C>(), 2);
+    assert!(!entities.contains(e0));
+    assert!(!entities.contains(e1));
+    assert!(entities.contains(e2));
+    assert!(entities.contains(e3));
+
+    // Other components of the destroyed entities are removed too
+    entities.run(|a: Comp<A>, b: Comp<B>, c: Comp<C>| {
+        assert_eq!(a.entities(), &[e2]);
+        assert_eq!(b.len(), 2);
+        assert!(c.is_empty());
+        assert_eq!((&a, &b).group_entities(), Some([e2].as_slice()));
+    });
+
+    assert_eq!(entities.destroy_all_with::<C>(), 0);
+    assert_eq!(entities.destroy_all_with::<D>(), 0);
+}
+
+#[test]
+fn test_entities_create_n() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    let mut next = 0;
+    let created = entities
+        .create_n(100, || {
+            next += 1;
+            (A(next),)
+        })
+        .to_vec();
+
+    assert_eq!(created.len(), 100);
+    assert_eq!(entities.entities(), created.as_slice());
+
+    // Each entity gets its own value
+    entities.run(|a: Comp<A>| {
+        let values = created.iter().map(|&e| a[e].0).collect::<Vec<_>>();
+        assert_eq!(values, (1..=100).collect::<Vec<_>>());
+    });
+
+    assert!(entities.create_n(0, || (A(0),)).is_empty());
+}
+
+#[test]
+fn test_entities_limit() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.set_entity_limit(Some(3));
+    assert_eq!(entities.entity_limit(), Some(3));
+
+    let e0 = entities.create((A(0),));
+    let e1 = entities.checked_create((A(1),)).unwrap();
+    let e2 = entities.checked_create_atomic().unwrap();
+
+    // Creation fails cleanly once the limit is reached
+    assert_eq!(entities.checked_create((A(3),)), Err(EntityLimitError));
+    assert_eq!(entities.checked_create_atomic(), Err(EntityLimitError));
+
+    entities.maintain();
+    assert_eq!(entities.entities(), [e0, e1, e2]);
+    assert_eq!(entities.get_component::<A>(e1), Some(&A(1)));
+    assert!(entities.insert(e2, (A(2),)));
+
+    // Destroyed entities free up room
+    assert!(entities.destroy(e0));
+    let e3 = entities.checked_create((A(3),)).unwrap();
+    assert_eq!(e3.index, e0.index);
+    assert_eq!(entities.checked_create(()), Err(EntityLimitError));
+
+    entities.set_entity_limit(None);
+    assert!(entities.checked_create(()).is_ok());
+}
+
+#[test]
+fn test_entities_limit_recycled() {
+    let mut entities = EntityStorage::default();
+    let created = entities.create_n(10, || ()).to_vec();
+
+    for &entity in &created[1..] {
+        entities.destroy(entity);
+    }
+
+    // Recycled indexes are checked against the limit
+    entities.set_entity_limit(Some(3));
+    let e1 = entities.checked_create(()).unwrap();
+    let e2 = entities.checked_create_atomic().unwrap();
+    assert!(e1.index < 3 && e2.index < 3);
+    assert_eq!(entities.checked_create(()), Err(EntityLimitError));
+    assert_eq!(entities.checked_create_atomic(), Err(EntityLimitError));
+
+    entities.maintain();
+    assert_eq!(entities.entities().len(), 3);
+
+    // Raising the limit makes the remaining indexes available again
+    entities.set_entity_limit(None);
+    let e3 = entities.checked_create(()).unwrap();
+    assert!(e3.index >= 3 && e3.index < 10);
+}
+
+#[test]
+#[should_panic]
+fn test_entities_limit_create_panics() {
+    let mut entities = EntityStorage::default();
+    entities.set_entity_limit(Some(0));
+    entities.create(());
+}
@@ -0,0 +1,84 @@
+//! Tests for changing the group layout of a storage.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+
+fn populate(entities: &mut EntityStorage) {
+    entities.register::<A>();
+    entities.register::<B>();
+    entities.register::<C>();
+    entities.register::<D>();
+
+    for i in 0..20 {
+        let e = entities.create((A(i),));
+
+        if i % 2 == 0 {
+            entities.insert(e, (B(i),));
+        }
+
+        if i % 3 == 0 {
+            entities.insert(e, (C(i), D(i)));
+        }
+    }
+}
+
+fn sorted_group_entities<'a, Q>(query: Q) -> Vec<Entity>
+where
+    Q: Query + 'a,
+{
+    let mut entities = query.group_entities().unwrap().to_vec();
+    entities.sort_unstable();
+    entities
+}
+
+#[test]
+fn test_set_layout_incremental() {
+    let layout_1 = GroupLayout::builder().add_group::<(A, B)>().build();
+
+    let layout_2 = GroupLayout::builder()
+        .add_group::<(A, B)>()
+        .add_group::<(C, D)>()
+        .build();
+
+    let mut entities = EntityStorage::new(&layout_1);
+    populate(&mut entities);
+
+    let (a_before, b_before) =
+        entities.run(|a: Comp<A>, b: Comp<B>| (a.entities().to_vec(), b.entities().to_vec()));
+
+    entities.set_layout(&layout_2);
+
+    // The unchanged family is not regrouped
+    entities.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!(a.entities(), a_before);
+        assert_eq!(b.entities(), b_before);
+    });
+
+    // The grouping matches a storage created with the new layout
+    let mut expected = EntityStorage::new(&layout_2);
+    populate(&mut expected);
+
+    let groups = |storage: &EntityStorage| {
+        storage.run(|a: Comp<A>, b: Comp<B>, c: Comp<C>, d: Comp<D>| {
+            (
+                sorted_group_entities((&a, &b)),
+                sorted_group_entities((&c, &d)),
+            )
+        })
+    };
+
+    let (ab, cd) = groups(&entities);
+    assert_eq!((ab.len(), cd.len()), (10, 7));
+    assert_eq!((ab, cd), groups(&expected));
+
+    // Switching back removes the second family
+    entities.set_layout(&layout_1);
+
+    entities.run(|a: Comp<A>, b: Comp<B>, c: Comp<C>, d: Comp<D>| {
+        assert_eq!(a.entities(), a_before);
+        assert!((&c, &d).group_entities().is_none());
+        assert_eq!(sorted_group_entities((&a, &b)).len(), 10);
+    });
+}
@@ -0,0 +1,102 @@
+//! Tests for inspecting entities.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use std::any;
+
+#[test]
+fn test_inspect_entity() {
+    let mut entities = EntityStorage::default();
+    entities.register_debug::<A>();
+    entities.register_debug::<B>();
+    entities.register::<C>();
+
+    let e0 = entities.create((A(0), B(1), C(2)));
+    let e1 = entities.create((A(3),));
+
+    let inspection = entities.inspect_entity(e0).unwrap();
+    assert_eq!(inspection.entity(), e0);
+    assert_eq!(inspection.components().len(), 3);
+
+    let debug = |type_name: &str| {
+        inspection
+            .components()
+            .iter()
+            .find(|component| component.type_name() == type_name)
+            .map(|component| component.debug())
+    };
+
+    assert_eq!(debug(any::type_name::<A>()), Some(Some("A(0)")));
+    assert_eq!(debug(any::type_name::<B>()), Some(Some("B(1)")));
+    assert_eq!(debug(any::type_name::<C>()), Some(None));
+
+    let inspection = entities.inspect_entity(e1).unwrap();
+    assert_eq!(inspection.components().len(), 1);
+    assert_eq!(inspection.components()[0].debug(), Some("A(3)"));
+
+    // Missing entities
+    entities.destroy(e1);
+    assert!(entities.inspect_entity(e1).is_none());
+}
+
+#[test]
+fn test_registered_components() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+
+    entities.create((A(0), B(0), C(0)));
+    entities.create((A(1), C(1)));
+    entities.create((A(2),));
+
+    let mut infos = entities.registered_components().collect::<Vec<_>>();
+    infos.sort_by_key(|info| info.type_name());
+
+    let summary = infos
+        .iter()
+        .map(|info| (info.type_name(), info.len(), info.is_grouped()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        summary,
+        [
+            (any::type_name::<A>(), 3, true),
+            (any::type_name::<B>(), 1, true),
+            (any::type_name::<C>(), 2, false),
+        ],
+    );
+}
+
+#[test]
+fn test_component_histogram() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+    entities.register::<D>();
+
+    assert!(entities.component_histogram().is_empty());
+
+    entities.create(());
+    entities.create((A(0),));
+    entities.create((B(0),));
+    entities.create((A(0), B(0)));
+    entities.create((A(0), B(0), C(0)));
+    entities.create((A(0), C(0), D(0)));
+    let e = entities.create((A(0), B(0), C(0), D(0)));
+
+    let histogram = entities.component_histogram();
+    assert_eq!(
+        histogram.into_iter().collect::<Vec<_>>(),
+        [(0, 1), (1, 2), (2, 1), (3, 2), (4, 1)]
+    );
+
+    entities.destroy(e);
+
+    let histogram = entities.component_histogram();
+    assert_eq!(
+        histogram.into_iter().collect::<Vec<_>>(),
+        [(0, 1), (1, 2), (2, 1), (3, 2)]
+    );
+}
@@ -1,9 +1,13 @@
+//! Tests for sparse and dense query iterators.
+
 mod common;
 
 use common::*;
 use sparsey::prelude::*;
+use sparsey::util::TypeData;
 use std::collections::HashSet;
 use std::iter::FromIterator;
+use std::ops::ControlFlow;
 
 #[test]
 fn test_sparse() {
@@ -91,3 +95,413 @@ fn test_dense() {
     let e = i.with_entity().map(|(e, _)| e).collect::<HashSet<_>>();
     assert_eq!(e, HashSet::from_iter([e1]));
 }
+
+#[test]
+fn test_sorted() {
+    let mut entities_1 = EntityStorage::default();
+    entities_1.register::<A>();
+    entities_1.register::<B>();
+
+    let mut entities_2 = EntityStorage::default();
+    entities_2.register::<A>();
+    entities_2.register::<B>();
+
+    let e = entities_1.extend((0..5).map(|_| ())).to_vec();
+    assert_eq!(entities_2.extend((0..5).map(|_| ())), e);
+
+    // Insert components in different orders
+    for (i, &entity) in e.iter().enumerate() {
+        entities_1.insert(entity, (A(i as u32), B(i as u32)));
+    }
+
+    for (i, &entity) in e.iter().enumerate().rev() {
+        entities_2.insert(entity, (A(i as u32), B(i as u32)));
+    }
+
+    // Shuffle the dense arrays of the first storage
+    assert_eq!(entities_1.remove::<(A,)>(e[1]), (Some(A(1)),));
+    entities_1.insert(e[1], (A(1),));
+
+    let a_1 = entities_1.borrow::<A>();
+    let b_1 = entities_1.borrow::<B>();
+    let mut a_2 = entities_2.borrow_mut::<A>();
+    let b_2 = entities_2.borrow::<B>();
+
+    assert_ne!(a_1.entities(), a_2.entities());
+
+    let sorted_1 = (&a_1, &b_1)
+        .sorted()
+        .with_entity()
+        .map(|(e, (a, b))| (e, *a, *b))
+        .collect::<Vec<_>>();
+
+    let sorted_2 = (&mut a_2, &b_2)
+        .sorted()
+        .with_entity()
+        .map(|(e, (a, b))| (e, *a, *b))
+        .collect::<Vec<_>>();
+
+    let expected = e
+        .iter()
+        .enumerate()
+        .map(|(i, &e)| (e, A(i as u32), B(i as u32)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(sorted_1, expected);
+    assert_eq!(sorted_2, expected);
+}
+
+#[test]
+fn test_iter_entities() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    let e0 = entities.create(());
+    let e1 = entities.create((A(1),));
+    let e2 = entities.create(());
+    entities.destroy(e0);
+
+    let view = entities.borrow_entities();
+    assert_eq!(view.iter().count(), entities.entities().len());
+    assert_eq!(view.iter().collect::<Vec<_>>(), view.as_slice());
+
+    let mut iterated = Vec::new();
+    for entity in &view {
+        iterated.push(entity);
+    }
+    assert_eq!(iterated, entities.entities());
+
+    // Dense indexes
+    let mut iter = view.iter();
+    iter.next();
+    let with_index = iter.with_index().collect::<Vec<_>>();
+    assert_eq!(with_index.len(), 1);
+    assert_eq!(view.as_slice()[with_index[0].0], with_index[0].1);
+
+    assert!([e1, e2]
+        .iter()
+        .all(|&e| view.iter().any(|entity| entity == e)));
+}
+
+#[test]
+fn test_for_each_while() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+
+    let e = entities.extend((0..10).map(|i| (A(i), B(i)))).to_vec();
+
+    let (a, mut b) = (entities.borrow::<A>(), entities.borrow_mut::<B>());
+
+    // Stop at the first matching element
+    let mut visited = 0;
+    let result = (&a, &mut b).for_each_while(|(a, b)| {
+        visited += 1;
+
+        if a.0 == 4 {
+            return ControlFlow::Break(a.0);
+        }
+
+        b.0 += 100;
+        ControlFlow::Continue(())
+    });
+
+    assert_eq!(result, ControlFlow::Break(4));
+    assert_eq!(visited, 5);
+    assert_eq!(b[e[3]], B(103));
+    assert_eq!(b[e[4]], B(4));
+    assert_eq!(b[e[5]], B(5));
+
+    // Same, but with entities
+    let result = (&a, &b).for_each_while_with_entity(|(entity, (a, _))| {
+        if a.0 == 7 {
+            ControlFlow::Break(entity)
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+
+    assert_eq!(result, ControlFlow::Break(e[7]));
+
+    // Run to completion when never breaking
+    let mut visited = 0;
+    let result = (&a, &b).for_each_while(|_| {
+        visited += 1;
+        ControlFlow::<()>::Continue(())
+    });
+
+    assert_eq!(result, ControlFlow::Continue(()));
+    assert_eq!(visited, 10);
+}
+
+#[test]
+fn test_query_plan() {
+    let layout = GroupLayout::builder()
+        .add_group::<(A, B)>()
+        .add_group::<(A, B, C)>()
+        .build();
+
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<D>();
+
+    entities.create((A(0), B(0)));
+    entities.create((A(1), B(1), C(1)));
+    entities.create((A(2), B(2), C(2), D(2)));
+    entities.create((A(3),));
+
+    let (a, b, c, d) = (
+        entities.borrow::<A>(),
+        entities.borrow::<B>(),
+        entities.borrow::<C>(),
+        entities.borrow::<D>(),
+    );
+
+    // Grouped queries take the dense path
+    let plan = (&a, &b).plan();
+    assert!(plan.is_dense());
+    assert_eq!(plan.entities_visited(), 3);
+
+    let plan = (&a, &b, &c).plan();
+    assert!(plan.is_dense());
+    assert_eq!(plan.entities_visited(), 2);
+
+    let plan = (&a, &b).exclude(&c).plan();
+    assert!(plan.is_dense());
+    assert_eq!(plan.entities_visited(), 1);
+    assert_eq!(plan.driving_component(), None);
+
+    // Ungrouped queries fall back to the sparse path, driven by the smallest storage
+    let plan = (&a, &d).plan();
+    assert!(plan.is_sparse());
+    assert!(plan.entities_visited() >= (&a, &d).iter().count());
+    assert_eq!(plan.driving_component(), Some(TypeData::new::<D>()));
+    assert_eq!(plan.entities_visited(), 1);
+}
+
+#[test]
+fn test_query_count() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+    entities.register::<D>();
+
+    for i in 0..20 {
+        let e = entities.create((A(i),));
+
+        if i % 2 == 0 {
+            entities.insert(e, (B(i),));
+        }
+
+        if i % 3 == 0 {
+            entities.insert(e, (C(i),));
+        }
+
+        if i % 5 == 0 {
+            entities.insert(e, (D(i),));
+        }
+    }
+
+    let (a, b, c, d) = (
+        entities.borrow::<A>(),
+        entities.borrow::<B>(),
+        entities.borrow::<C>(),
+        entities.borrow::<D>(),
+    );
+
+    fn iterations<'a, Q>(query: Q) -> usize
+    where
+        Q: Query + 'a,
+    {
+        let mut iterations = 0;
+        query.for_each(|_| iterations += 1);
+        iterations
+    }
+
+    // Single component
+    assert_eq!(Query::count(&c), c.len());
+    assert_eq!(Query::count(&c), iterations(&c));
+
+    // Grouped
+    assert_eq!((&a, &b).count(), 10);
+    assert_eq!((&a, &b).count(), iterations((&a, &b)));
+
+    // Ungrouped with filters
+    assert_eq!((&a, &c).count(), iterations((&a, &c)));
+    assert_eq!((&a, &c).include(&d).count(), 2);
+    assert_eq!(
+        (&a, &c).include(&d).count(),
+        iterations((&a, &c).include(&d))
+    );
+    assert_eq!((&a, &c).exclude(&d).count(), 5);
+    assert_eq!(
+        (&a, &c).exclude(&d).count(),
+        iterations((&a, &c).exclude(&d))
+    );
+    assert_eq!(
+        (&b, &c).exclude(&d).count(),
+        iterations((&b, &c).exclude(&d))
+    );
+}
+
+#[test]
+fn test_iter_combinations() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+
+    for i in 0..4 {
+        entities.create((A(i), B(i)));
+    }
+
+    entities.create((A(4),));
+
+    let (a, b) = (entities.borrow::<A>(), entities.borrow::<B>());
+
+    let mut pairs = (&a, &b)
+        .iter_combinations::<2>()
+        .map(|[(a0, _), (a1, _)]| (a0.0.min(a1.0), a0.0.max(a1.0)))
+        .collect::<Vec<_>>();
+
+    // Each unordered pair appears exactly once and never pairs an entity with itself
+    pairs.sort_unstable();
+    assert_eq!(pairs, [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3)]);
+
+    assert_eq!((&a, &b).iter_combinations::<3>().count(), 4);
+    assert_eq!((&a, &b).iter_combinations::<5>().count(), 0);
+    assert_eq!(a.iter_combinations::<2>().count(), 10);
+}
+
+#[test]
+fn test_for_each_with_entity_parity() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+
+    let e = entities
+        .extend((0..10).map(|i| (A(i), B(i), C(i))))
+        .to_vec();
+
+    // Swap-remove a few components to shuffle the dense arrays
+    entities.delete::<(A, C)>(e[2]);
+    entities.delete::<(B, C)>(e[5]);
+    entities.delete::<(C,)>(e[0]);
+
+    let (a, b, c) = (
+        entities.borrow::<A>(),
+        entities.borrow::<B>(),
+        entities.borrow::<C>(),
+    );
+
+    // Dense
+    let (group_entities, (group_a, _)) = (&a, &b).group_data().unwrap();
+    let expected = group_entities
+        .iter()
+        .zip(group_a)
+        .map(|(&entity, a)| (entity, a.0))
+        .collect::<Vec<_>>();
+
+    let mut dense = Vec::new();
+    (&a, &b).for_each_with_entity(|(entity, (a, _))| dense.push((entity, a.0)));
+    assert_eq!(dense, expected);
+
+    // Sparse
+    let mut sparse = Vec::new();
+    (&a, &b)
+        .include(&c)
+        .for_each_with_entity(|(entity, (a, _))| sparse.push((entity, a.0)));
+
+    assert_eq!(sparse.len(), 7);
+
+    for (entity, value) in sparse {
+        assert_eq!(a[entity].0, value);
+        assert!(c.contains(entity));
+    }
+}
+
+#[test]
+fn test_iter_many() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<B>();
+    entities.register::<C>();
+
+    let e0 = entities.create((A(0), B(0)));
+    let e1 = entities.create((A(1), B(1)));
+    let e2 = entities.create((A(2), B(2), C(2)));
+    let e3 = entities.create((A(3),));
+    let stale = entities.create((A(4), B(4)));
+    entities.destroy(stale);
+    let e4 = entities.create((A(5), B(5)));
+
+    // Missing, stale and excluded entities are skipped, order and duplicates are kept
+    let (a, b, c) = (
+        entities.borrow::<A>(),
+        entities.borrow::<B>(),
+        entities.borrow::<C>(),
+    );
+    let values = (&a, &b)
+        .exclude(&c)
+        .iter_many([e1, e3, stale, e0, e2, e1, e4])
+        .map(|(a, _)| a.0)
+        .collect::<Vec<_>>();
+
+    assert_eq!(values, [1, 0, 1, 5]);
+    drop((a, b, c));
+
+    entities.run(|mut a: CompMut<A>, b: Comp<B>| {
+        (&mut a, &b)
+            .iter_many_mut([e4, stale, e3, e0])
+            .for_each(|(a, b)| a.0 += b.0 * 10);
+    });
+
+    let a = entities.borrow::<A>();
+    assert_eq!(a.iter().map(|a| a.0).collect::<Vec<_>>(), [0, 1, 2, 3, 55]);
+}
+
+#[test]
+#[should_panic]
+fn test_iter_many_mut_duplicate() {
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+
+    let e0 = entities.create((A(0),));
+    let e1 = entities.create((A(1),));
+
+    entities.run(|mut a: CompMut<A>| {
+        (&mut a).iter_many_mut([e0, e1, e0]).for_each(|_| ());
+    });
+}
+
+#[test]
+fn test_sorted_by_key() {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    struct Depth(f32);
+
+    let mut entities = EntityStorage::default();
+    entities.register::<A>();
+    entities.register::<Depth>();
+
+    let e0 = entities.create((A(0), Depth(2.5)));
+    let e1 = entities.create((A(1), Depth(-1.0)));
+    let e2 = entities.create((A(2), Depth(2.5)));
+    let e3 = entities.create((A(3), Depth(0.0)));
+    entities.create((A(4),));
+
+    let (a, depth) = (entities.borrow::<A>(), entities.borrow::<Depth>());
+
+    // Entities with equal depths keep their relative order
+    let sorted = (&a, &depth)
+        .sorted_by(|(_, d0), (_, d1)| d0.0.total_cmp(&d1.0))
+        .with_entity()
+        .map(|(e, (_, d))| (e, d.0))
+        .collect::<Vec<_>>();
+
+    assert_eq!(sorted, [(e1, -1.0), (e3, 0.0), (e0, 2.5), (e2, 2.5)]);
+
+    let sorted = a
+        .sorted_by_key(|a| std::cmp::Reverse(a.0))
+        .map(|a| a.0)
+        .collect::<Vec<_>>();
+
+    assert_eq!(sorted, [4, 3, 2, 1, 0]);
+}
@@ -0,0 +1,38 @@
+//! Tests for creating entities in parallel.
+
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use common::*;
+use rayon::prelude::*;
+use sparsey::prelude::*;
+
+#[test]
+fn test_par_extend() {
+    let mut sequential = EntityStorage::default();
+    sequential.register::<A>();
+    sequential.register::<B>();
+
+    let mut parallel = EntityStorage::default();
+    parallel.register::<A>();
+    parallel.register::<B>();
+
+    let make = |i: u32| (A(i), B(i * i));
+
+    let e_seq = sequential.extend((0..10_000).map(make)).to_vec();
+    let e_par = parallel
+        .par_extend((0..10_000).into_par_iter().map(make))
+        .to_vec();
+
+    assert_eq!(e_par.len(), 10_000);
+    assert_eq!(parallel.entities().len(), 10_000);
+    assert_eq!(e_seq, e_par);
+
+    sequential.run(|a_seq: Comp<A>, b_seq: Comp<B>| {
+        parallel.run(|a_par: Comp<A>, b_par: Comp<B>| {
+            assert_eq!(a_seq.as_slice(), a_par.as_slice());
+            assert_eq!(b_seq.as_slice(), b_par.as_slice());
+        });
+    });
+}
@@ -0,0 +1,96 @@
+//! Tests for iterating queries in parallel.
+
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use common::*;
+use rayon::prelude::*;
+use sparsey::prelude::*;
+use sparsey::query::ParIter;
+
+#[test]
+fn test_par_iter() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.register::<C>();
+
+    let e = entities
+        .extend((0..10_000).map(|i| (A(i), B(i % 7))))
+        .to_vec();
+
+    for &entity in e.iter().step_by(3) {
+        entities.insert(entity, (C(1),));
+    }
+
+    entities.run(|a: Comp<A>, b: Comp<B>, c: Comp<C>| {
+        // Dense
+        let sequential = (&a, &b)
+            .iter()
+            .fold(0_u64, |sum, (a, b)| sum + u64::from(a.0 * b.0));
+        let parallel = (&a, &b)
+            .par_iter()
+            .map(|(a, b)| u64::from(a.0 * b.0))
+            .reduce(|| 0, |x, y| x + y);
+
+        assert!((&a, &b).iter().is_dense());
+        assert_eq!(parallel, sequential);
+
+        // Sparse
+        let sequential = (&a, &b)
+            .exclude(&c)
+            .iter()
+            .fold(0_u64, |sum, (a, _)| sum + u64::from(a.0));
+        let parallel = (&a, &b)
+            .exclude(&c)
+            .par_iter()
+            .map(|(a, _)| u64::from(a.0))
+            .sum::<u64>();
+
+        assert!((&a, &b).exclude(&c).iter().is_sparse());
+        assert_eq!(parallel, sequential);
+        assert_eq!((&a, &b).exclude(&c).par_iter().count(), 6_666);
+    });
+
+    entities.run(|mut a: CompMut<A>, c: Comp<C>| {
+        (&mut a).include(&c).par_iter().for_each(|a| a.0 = 0);
+    });
+
+    let a = entities.borrow::<A>();
+    assert_eq!(a.iter().filter(|a| a.0 == 0).count(), 3_334);
+}
+
+#[test]
+fn test_par_iter_indexed() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut entities = EntityStorage::new(&layout);
+    entities.extend((0..1_000).map(|i| (A(i), B(i * 2))));
+
+    let a = entities.borrow::<A>();
+    let b = entities.borrow::<B>();
+
+    let ParIter::Dense(dense) = (&a, &b).par_iter() else {
+        panic!("Grouped queries should be dense");
+    };
+
+    let mut sums = Vec::new();
+    dense.map(|(a, b)| a.0 + b.0).collect_into_vec(&mut sums);
+    assert_eq!(sums, (0..1_000).map(|i| i * 3).collect::<Vec<_>>());
+
+    let ParIter::Dense(dense) = (&a, &b).par_iter() else {
+        panic!("Grouped queries should be dense");
+    };
+
+    assert_eq!(dense.len(), 1_000);
+    assert!(dense
+        .enumerate()
+        .zip(0..1_000_u32)
+        .all(|((i, (a, b)), j)| i as u32 == j && a.0 == j && b.0 == j * 2));
+
+    // Single component views
+    assert!((&a).into_par_iter().is_dense());
+    assert_eq!(
+        b.into_par_iter().map(|b| b.0).sum::<u32>(),
+        (0..2_000).step_by(2).sum()
+    );
+}
@@ -1,7 +1,11 @@
+//! Tests for inserting and removing resources.
+
 mod common;
 
 use common::*;
 use sparsey::prelude::*;
+use sparsey::resource::SharedResource;
+use std::panic::{self, AssertUnwindSafe};
 
 #[test]
 fn test_resources_crud() {
@@ -59,3 +63,157 @@ fn test_resources_crud() {
     assert!(resources.try_borrow::<A>().is_none());
     assert!(resources.try_borrow::<B>().is_none());
 }
+
+#[test]
+fn test_resources_get_many_mut() {
+    let mut resources = ResourceStorage::default();
+    resources.insert(A(0));
+    resources.insert(B(1));
+    resources.insert(C(2));
+
+    let (a, b, c) = resources.get_many_mut::<(A, B, C)>();
+    a.0 += 10;
+    b.0 += 10;
+    c.0 += 10;
+
+    assert_eq!(*resources.borrow::<A>(), A(10));
+    assert_eq!(*resources.borrow::<B>(), B(11));
+    assert_eq!(*resources.borrow::<C>(), C(12));
+
+    // Missing resources
+    assert!(resources.try_get_many_mut::<(A, D)>().is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_resources_get_many_mut_duplicate() {
+    let mut resources = ResourceStorage::default();
+    resources.insert(A(0));
+
+    let _ = resources.get_many_mut::<(A, A)>();
+}
+
+#[test]
+#[should_panic(expected = "resource of type 'resources_crud::common::D'")]
+fn test_resources_get_many_mut_missing() {
+    let mut resources = ResourceStorage::default();
+    resources.insert(A(0));
+
+    let _ = resources.get_many_mut::<(A, D)>();
+}
+
+#[test]
+fn test_resources_set_if_neq() {
+    let mut resources = ResourceStorage::default();
+    resources.insert(A(0));
+
+    let mut a = resources.borrow_mut::<A>();
+    assert!(!a.set_if_neq(A(0)));
+    assert!(a.set_if_neq(A(1)));
+    assert_eq!(*a, A(1));
+}
+
+#[test]
+fn test_resources_shared() {
+    let mut main_world = World::default();
+    let mut render_world = World::default();
+
+    let shared = SharedResource::new(A(0));
+    assert!(main_world.resources.insert_shared(shared.clone()).is_none());
+    assert!(render_world.resources.insert_shared(shared).is_none());
+
+    // Write in one world
+    main_world.run(|mut a: ResMut<SharedResource<A>>| {
+        a.borrow_mut().0 = 10;
+    });
+
+    // Observe in the other world
+    render_world.run(|a: Res<SharedResource<A>>| {
+        assert_eq!(*a.borrow(), A(10));
+    });
+
+    let main_a = main_world.resources.borrow::<SharedResource<A>>();
+    let render_a = render_world.resources.borrow::<SharedResource<A>>();
+    assert!(main_a.ptr_eq(&render_a));
+}
+
+#[test]
+fn test_resources_map() {
+    struct Settings {
+        volume: u32,
+        name: &'static str,
+    }
+
+    let mut resources = ResourceStorage::default();
+    resources.insert(Settings {
+        volume: 5,
+        name: "default",
+    });
+
+    let volume = Res::map(resources.borrow::<Settings>(), |s| &s.volume);
+    assert_eq!(*volume, 5);
+
+    // The original borrow is still held
+    let borrow_mut = || drop(resources.borrow_mut::<Settings>());
+    assert!(panic::catch_unwind(AssertUnwindSafe(borrow_mut)).is_err());
+    drop(volume);
+
+    let mut name = ResMut::map(resources.borrow_mut::<Settings>(), |s| &mut s.name);
+    *name = "custom";
+    drop(name);
+
+    let settings = resources.borrow::<Settings>();
+    assert_eq!((settings.volume, settings.name), (5, "custom"));
+}
+
+#[test]
+fn test_resources_replace_with() {
+    enum State {
+        Loading(Vec<u32>),
+        Ready(u32),
+    }
+
+    let mut resources = ResourceStorage::default();
+
+    // Missing resource
+    assert!(!resources.replace_with(|state: State| state));
+
+    // Consume the current value to build the new one
+    resources.insert(State::Loading(vec![1, 2, 3]));
+
+    assert!(resources.replace_with(|state| {
+        match state {
+            State::Loading(values) => State::Ready(values.into_iter().sum()),
+            state => state,
+        }
+    }));
+
+    assert!(matches!(*resources.borrow::<State>(), State::Ready(6)));
+}
+
+#[test]
+fn test_resources_named() {
+    #[derive(Default, PartialEq, Eq, Debug)]
+    struct Gravity(u32);
+
+    let mut resources = ResourceStorage::default();
+    assert!(resources.register_named::<Gravity>("gravity"));
+    assert!(!resources.register_named::<u32>("gravity"));
+
+    // Unknown names insert nothing
+    assert!(!resources.insert_by_name("wind"));
+    assert!(resources.is_empty());
+
+    // Registered names insert the default value
+    assert!(resources.insert_by_name("gravity"));
+    assert_eq!(*resources.borrow::<Gravity>(), Gravity(0));
+
+    resources.get_mut::<Gravity>().0 = 10;
+    assert!(resources.insert_by_name("gravity"));
+    assert_eq!(*resources.borrow::<Gravity>(), Gravity(0));
+
+    // Names survive clearing the storage
+    resources.clear();
+    assert!(resources.insert_by_name("gravity"));
+    assert!(resources.contains::<Gravity>());
+}
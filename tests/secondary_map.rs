@@ -0,0 +1,89 @@
+//! Tests for maps from entities to values stored outside the world.
+
+use sparsey::prelude::*;
+use sparsey::util::SecondaryMap;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn test_secondary_map() {
+    let mut entities = EntityStorage::default();
+    let e0 = entities.create(());
+    let e1 = entities.create(());
+    let e2 = entities.create(());
+
+    let mut map = SecondaryMap::new();
+    assert!(map.is_empty());
+
+    // Insert and replace
+    assert_eq!(map.insert(e0, "a"), None);
+    assert_eq!(map.insert(e1, "b"), None);
+    assert_eq!(map.insert(e2, "c"), None);
+    assert_eq!(map.insert(e1, "bb"), Some("b"));
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(e1), Some(&"bb"));
+
+    // Iteration visits every entry once
+    let mut items = map.iter().map(|(e, &v)| (e, v)).collect::<Vec<_>>();
+    items.sort_unstable_by_key(|&(e, _)| e);
+    assert_eq!(items, [(e0, "a"), (e1, "bb"), (e2, "c")]);
+
+    // Removal keeps the remaining entries reachable
+    assert_eq!(map.remove(e0), Some("a"));
+    assert_eq!(map.remove(e0), None);
+    assert_eq!(map.get(e1), Some(&"bb"));
+    assert_eq!(map.get(e2), Some(&"c"));
+
+    *map.get_mut(e2).unwrap() = "cc";
+    assert_eq!(map.values().len(), map.entities().len());
+
+    // Recycled entities don't match stale entries
+    entities.destroy(e1);
+    let e3 = entities.create(());
+    assert_eq!(e3.index, e1.index);
+    assert!(!map.contains(e3));
+    assert_eq!(map.get(e3), None);
+    assert_eq!(map.remove(e3), None);
+
+    // Inserting a recycled entity drops the stale entry
+    assert_eq!(map.insert(e3, "d"), None);
+    assert!(!map.contains(e1));
+    assert_eq!(map.get(e3), Some(&"d"));
+
+    // Purge entries of destroyed entities
+    entities.destroy(e2);
+    map.retain(|entity, _| entities.contains(entity));
+    assert_eq!(map.entities(), &[e3]);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert!(!map.contains(e3));
+}
+
+#[test]
+fn test_secondary_map_on_destroy() {
+    let map = Arc::new(Mutex::new(SecondaryMap::new()));
+
+    let mut entities = EntityStorage::default();
+    entities.on_destroy({
+        let map = map.clone();
+        move |entity| {
+            map.lock().unwrap().remove(entity);
+        }
+    });
+
+    let e = entities.extend((0..4).map(|_| ())).to_vec();
+    for &entity in &e {
+        map.lock().unwrap().insert(entity, entity.index);
+    }
+
+    entities.destroy(e[1]);
+    assert_eq!(map.lock().unwrap().get(e[1]), None);
+    assert_eq!(map.lock().unwrap().len(), 3);
+
+    // Hooks are kept after restoring a snapshot
+    let snapshot = entities.snapshot().unwrap();
+    entities.restore(snapshot);
+
+    entities.clear();
+    assert!(map.lock().unwrap().is_empty());
+}
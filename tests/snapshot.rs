@@ -0,0 +1,63 @@
+//! Tests for taking and restoring snapshots.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::util::TypeData;
+
+#[test]
+fn test_snapshot_restore() {
+    let layout = GroupLayout::builder().add_group::<(A, B)>().build();
+    let mut world = World::new(&layout);
+    world.entities.register_cloneable::<A>();
+    world.entities.register_cloneable::<B>();
+    world.resources.register_cloneable::<C>();
+    world.resources.insert(C(0));
+    world.resources.insert(D(0));
+
+    let e0 = world.entities.create((A(0), B(0)));
+    let e1 = world.entities.create((A(1),));
+
+    let snapshot = world.snapshot().unwrap();
+
+    // Mutate everything after the snapshot
+    let e2 = world.entities.create((A(2), B(2)));
+    world.entities.destroy(e0);
+    world.entities.insert(e1, (B(1),));
+    world.entities.get_component_mut::<A>(e1).unwrap().0 = 10;
+    world.resources.get_mut::<C>().0 = 1;
+    world.resources.get_mut::<D>().0 = 1;
+
+    world.restore(snapshot);
+
+    // Entities and components match the snapshot exactly
+    assert_eq!(world.entities.entities(), &[e0, e1]);
+    assert!(!world.entities.contains(e2));
+    assert_eq!(world.entities.get_component::<A>(e0), Some(&A(0)));
+    assert_eq!(world.entities.get_component::<B>(e0), Some(&B(0)));
+    assert_eq!(world.entities.get_component::<A>(e1), Some(&A(1)));
+    assert_eq!(world.entities.get_component::<B>(e1), None);
+
+    world.run(|a: Comp<A>, b: Comp<B>| {
+        assert_eq!((&a, &b).group_entities(), Some([e0].as_slice()));
+    });
+
+    // Cloneable resources are restored, others are left untouched
+    assert_eq!(*world.resources.borrow::<C>(), C(0));
+    assert_eq!(*world.resources.borrow::<D>(), D(1));
+
+    // The allocator is restored, so entity creation is deterministic
+    assert_eq!(world.entities.create(()), e2);
+}
+
+#[test]
+fn test_snapshot_non_cloneable() {
+    let mut entities = EntityStorage::default();
+    entities.register_cloneable::<A>();
+    entities.register::<B>();
+
+    let error = entities.snapshot().unwrap_err();
+    assert_eq!(error.type_data(), TypeData::new::<B>());
+    assert!(error.to_string().contains(error.type_name()));
+}
@@ -0,0 +1,107 @@
+//! Tests for systems with many parameters.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+
+#[test]
+fn test_system_8_params() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.entities.register::<C>();
+    world.entities.register::<D>();
+    world.resources.insert(A(1));
+    world.resources.insert(B(2));
+    world.resources.insert(C(3));
+
+    let e0 = world.entities.create((A(0), B(0), C(0), D(0)));
+
+    let mut system = System::new(
+        |entities: Entities,
+         mut a: CompMut<A>,
+         b: Comp<B>,
+         c: Comp<C>,
+         d: Comp<D>,
+         ra: Res<A>,
+         rb: Res<B>,
+         mut rc: ResMut<C>| {
+            for entity in &entities {
+                if (&b, &c, &d).matches(entity) {
+                    a[entity].0 += ra.0 + rb.0 + rc.0;
+                }
+            }
+
+            rc.0 += 1;
+        },
+    );
+
+    assert_eq!(system.params().len(), 8);
+
+    system.run(&world);
+    system.run(&world);
+
+    assert_eq!(world.entities.get_component::<A>(e0), Some(&A(6 + 7)));
+    assert_eq!(*world.resources.borrow::<C>(), C(5));
+}
+
+#[test]
+fn test_system_16_params() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.entities.register::<C>();
+    world.entities.register::<D>();
+    world.resources.insert(1_u8);
+    world.resources.insert(2_u16);
+    world.resources.insert(3_u32);
+    world.resources.insert(4_u64);
+    world.resources.insert(5_u128);
+    world.resources.insert(6_usize);
+    world.resources.insert(7_i8);
+    world.resources.insert(8_i16);
+    world.resources.insert(9_i64);
+    world.resources.insert(10_i128);
+    world.resources.insert(0_i32);
+
+    let e0 = world.entities.create((A(0), B(0), C(0), D(0)));
+    let e1 = world.entities.create((A(0), B(0)));
+
+    let system = |entities: Entities,
+                  mut a: CompMut<A>,
+                  b: Comp<B>,
+                  c: Comp<C>,
+                  d: Comp<D>,
+                  r0: Res<u8>,
+                  r1: Res<u16>,
+                  r2: Res<u32>,
+                  r3: Res<u64>,
+                  r4: Res<u128>,
+                  r5: Res<usize>,
+                  r6: Res<i8>,
+                  r7: Res<i16>,
+                  r8: Res<i64>,
+                  r9: Res<i128>,
+                  mut runs: ResMut<i32>| {
+        let sum = u32::from(*r0) + u32::from(*r1) + *r2 + *r3 as u32 + *r4 as u32;
+        let sum = sum + *r5 as u32 + *r6 as u32 + *r7 as u32 + *r8 as u32 + *r9 as u32;
+
+        for entity in &entities {
+            if (&b, &c, &d).matches(entity) {
+                a[entity].0 += sum;
+            }
+        }
+
+        *runs += 1;
+    };
+
+    assert_eq!(System::new(system).params().len(), 16);
+
+    world.run(system);
+    world.run(system);
+
+    assert_eq!(world.entities.get_component::<A>(e0), Some(&A(110)));
+    assert_eq!(world.entities.get_component::<A>(e1), Some(&A(0)));
+    assert_eq!(*world.resources.borrow::<i32>(), 2);
+}
@@ -0,0 +1,53 @@
+//! Tests for system parameters implemented outside the crate.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::system::{SystemBorrow, SystemParam, SystemParamKind};
+use sparsey::util::TypeData;
+
+/// Custom parameter that increments every component it borrows.
+struct Incrementer<'a>(CompMut<'a, A>);
+
+impl Incrementer<'_> {
+    fn increment_all(&mut self) {
+        self.0.as_mut_slice().iter_mut().for_each(|a| a.0 += 1);
+    }
+}
+
+impl SystemParam for Incrementer<'_> {
+    const KIND: SystemParamKind = SystemParamKind::CompMut(TypeData::new::<A>());
+
+    type Param<'a> = Incrementer<'a>;
+}
+
+impl SystemBorrow<World> for Incrementer<'_> {
+    fn borrow(world: &World) -> Self::Param<'_> {
+        Incrementer(world.entities.borrow_mut())
+    }
+}
+
+#[test]
+fn test_custom_mutable_param() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+
+    let e0 = world.entities.create((A(0),));
+    let e1 = world.entities.create((A(1),));
+
+    world.run(|mut incrementer: Incrementer| {
+        incrementer.increment_all();
+    });
+
+    world.run(|a: Comp<A>| {
+        assert_eq!(a.get(e0), Some(&A(1)));
+        assert_eq!(a.get(e1), Some(&A(2)));
+    });
+
+    let system = System::new(|_: Incrementer| ());
+    assert_eq!(
+        system.params(),
+        &[SystemParamKind::CompMut(TypeData::new::<A>())],
+    );
+}
@@ -0,0 +1,38 @@
+//! Tests for systems created from functions that capture state.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+
+fn add_system(layer: u32, amount: u32) -> System {
+    let mut runs = 0;
+
+    System::new(move |mut a: CompMut<A>| {
+        runs += 1;
+
+        if let Some(a) = a.as_mut_slice().get_mut(layer as usize) {
+            a.0 += amount * runs;
+        }
+    })
+}
+
+#[test]
+fn test_system_factory() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.extend([(A(0),), (A(0),), (A(0),)]);
+
+    let mut systems = [add_system(0, 1), add_system(1, 10), add_system(2, 100)];
+
+    for _ in 0..2 {
+        for system in &mut systems {
+            system.run(&world);
+        }
+    }
+
+    // Each system owns its captured state
+    world.run(|a: Comp<A>| {
+        assert_eq!(a.as_slice(), &[A(1 + 2), A(10 + 20), A(100 + 200)]);
+    });
+}
@@ -0,0 +1,74 @@
+//! Tests for borrowing conflicting system parameters through a `ParamSet`.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::system::{ParamSet, SystemParam, SystemParamKind};
+use sparsey::util::TypeData;
+
+type Params<'a, 'b> = ParamSet<'a, (CompMut<'b, A>, CompMut<'b, A>, Res<'b, B>, ResMut<'b, B>)>;
+
+#[test]
+fn test_param_set() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.resources.insert(B(1));
+
+    let e0 = world.entities.create((A(0),));
+    let e1 = world.entities.create((A(1),));
+
+    let mut system = System::new(|mut set: Params| {
+        let amount = set.p2().0;
+
+        for a in set.p0().as_mut_slice().iter_mut() {
+            a.0 += amount;
+        }
+
+        for a in set.p1().as_mut_slice().iter_mut() {
+            a.0 *= 10;
+        }
+
+        set.p3().0 += 1;
+    });
+
+    let a = TypeData::new::<A>();
+    let b = TypeData::new::<B>();
+
+    let [SystemParamKind::ParamSet(set)] = system.params() else {
+        panic!("Expected a single ParamSet");
+    };
+
+    assert_eq!(
+        *set,
+        [
+            SystemParamKind::CompMut(a),
+            SystemParamKind::CompMut(a),
+            SystemParamKind::Res(b),
+            SystemParamKind::ResMut(b),
+        ],
+    );
+
+    // Sets conflict with parameters that conflict with any of their members. Members within one
+    // set are not checked against each other.
+    let kind = system.params()[0];
+    assert!(kind.conflicts_with(SystemParamKind::Comp(a)));
+    assert!(SystemParamKind::Res(b).conflicts_with(kind));
+    assert!(!kind.conflicts_with(SystemParamKind::Comp(TypeData::new::<C>())));
+
+    // Sets conflict with other sets through their members
+    let set_b = <ParamSet<(Comp<C>, Res<B>)> as SystemParam>::KIND;
+    let set_c = <ParamSet<(Comp<C>, CompMut<C>)> as SystemParam>::KIND;
+    assert!(kind.conflicts_with(kind));
+    assert!(kind.conflicts_with(set_b));
+    assert!(set_b.conflicts_with(kind));
+    assert!(!kind.conflicts_with(set_c));
+    assert!(!set_c.conflicts_with(kind));
+
+    system.run(&world);
+    system.run(&world);
+
+    assert_eq!(world.entities.get_component::<A>(e0), Some(&A(120)));
+    assert_eq!(world.entities.get_component::<A>(e1), Some(&A(220)));
+    assert_eq!(*world.resources.borrow::<B>(), B(3));
+}
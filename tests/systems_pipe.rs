@@ -0,0 +1,40 @@
+//! Tests for piping the output of a system into another system.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+use sparsey::system::SystemParamKind;
+use sparsey::util::TypeData;
+
+#[test]
+fn test_system_pipe() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+    world.resources.insert(A(10));
+
+    world.entities.create((A(1), B(0)));
+    world.entities.create((A(2),));
+    world.entities.create((A(3), B(0)));
+
+    let mut system =
+        System::new(|a: Comp<A>, b: Comp<B>| -> u32 { (&a, &b).iter().map(|(a, _)| a.0).sum() })
+            .pipe(|In(sum): In<u32>, a: Comp<A>, mut ra: ResMut<A>| {
+                ra.0 += sum;
+                ra.0 * a.len() as u32
+            });
+
+    assert_eq!(
+        system.params(),
+        [
+            SystemParamKind::Comp(TypeData::new::<A>()),
+            SystemParamKind::Comp(TypeData::new::<B>()),
+            SystemParamKind::ResMut(TypeData::new::<A>()),
+        ],
+    );
+
+    assert_eq!(system.run(&world), 42);
+    assert_eq!(system.run(&world), 54);
+    assert_eq!(*world.resources.borrow::<A>(), A(18));
+}
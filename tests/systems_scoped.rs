@@ -0,0 +1,38 @@
+//! Tests for running functions that borrow local data.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+
+#[test]
+fn test_run_scoped() {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.entities.register::<B>();
+
+    world.entities.create((A(1), B(10)));
+    world.entities.create((A(2), B(20)));
+    world.entities.create((A(3),));
+
+    let mut sum = 0;
+    let scale = 2;
+
+    world.run(|mut a: CompMut<A>, b: Comp<B>| {
+        (&mut a, &b).for_each(|(a, b)| {
+            a.0 *= scale;
+            sum += a.0 + b.0;
+        });
+    });
+
+    assert_eq!(sum, 2 + 10 + 4 + 20);
+
+    let mut values = Vec::new();
+
+    world.run(|a: Comp<A>| {
+        values.extend(a.iter().map(|a| a.0));
+    });
+
+    values.sort_unstable();
+    assert_eq!(values, [2, 3, 4]);
+}
@@ -0,0 +1,39 @@
+//! Tests for clearing the storages of a world.
+
+mod common;
+
+use common::*;
+use sparsey::prelude::*;
+
+fn create_world() -> (World, Entity) {
+    let mut world = World::default();
+    world.entities.register::<A>();
+    world.resources.insert(B(0));
+
+    let e0 = world.entities.create((A(0),));
+    (world, e0)
+}
+
+#[test]
+fn test_world_clear_entities() {
+    let (mut world, e0) = create_world();
+    world.clear_entities();
+
+    assert!(world.entities.is_empty());
+    assert!(!world.entities.contains(e0));
+    assert!(world.resources.contains::<B>());
+
+    // Components stay registered
+    let e1 = world.entities.create((A(1),));
+    assert_eq!(world.entities.get_component::<A>(e1), Some(&A(1)));
+}
+
+#[test]
+fn test_world_clear_resources() {
+    let (mut world, e0) = create_world();
+    world.clear_resources();
+
+    assert!(world.resources.is_empty());
+    assert!(world.entities.contains(e0));
+    assert_eq!(world.entities.get_component::<A>(e0), Some(&A(0)));
+}